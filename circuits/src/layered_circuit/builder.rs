@@ -0,0 +1,236 @@
+//! Builder for layered circuits from arbitrary add/mul computations.
+//! Gates may take inputs from any earlier gate, the builder assigns every gate to a
+//! layer by its depth and relays values that skip layers with `x + 0`.
+//! Input 0 of the built circuit is reserved for the constant zero used by the relays,
+//! gates have no constants so it is up to the circuit to constrain it.
+use std::collections::HashMap;
+
+use super::{
+    LayeredCircuit,
+    primitives::{Gate, GateOp, Layer},
+};
+
+/// Handle to a value in the circuit under construction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wire(usize);
+
+/// Node of the computation graph
+#[derive(Debug, Clone)]
+enum Node {
+    /// Circuit input with the given index
+    Input(usize),
+    /// Gate over two earlier wires
+    Gate(GateOp, [Wire; 2]),
+}
+
+/// Builds a `LayeredCircuit` from a computation graph
+#[derive(Debug, Clone)]
+pub struct CircuitBuilder {
+    /// Nodes of the computation graph
+    nodes: Vec<Node>,
+    /// Depth of every node, inputs have depth 0
+    depths: Vec<usize>,
+    /// Number of circuit inputs, including the reserved zero
+    num_inputs: usize,
+}
+
+impl Default for CircuitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBuilder {
+    /// Creates a builder with input 0 reserved for the constant zero
+    pub fn new() -> Self {
+        let mut builder = Self {
+            nodes: vec![],
+            depths: vec![],
+            num_inputs: 0,
+        };
+        builder.input();
+        builder
+    }
+
+    /// The reserved zero input. It is a regular input, a circuit whose inputs are not
+    /// trusted should output it so the verifier can check it is zero.
+    pub fn zero(&self) -> Wire {
+        Wire(0)
+    }
+
+    /// Number of circuit inputs, including the reserved zero
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// Adds a new circuit input
+    pub fn input(&mut self) -> Wire {
+        self.num_inputs += 1;
+        self.push(Node::Input(self.num_inputs - 1), 0)
+    }
+
+    /// Adds `count` new circuit inputs
+    pub fn inputs(&mut self, count: usize) -> Vec<Wire> {
+        (0..count).map(|_| self.input()).collect()
+    }
+
+    /// Adds an ADD gate
+    pub fn add(&mut self, a: Wire, b: Wire) -> Wire {
+        self.gate(GateOp::Add, a, b)
+    }
+
+    /// Adds a MUL gate
+    pub fn mul(&mut self, a: Wire, b: Wire) -> Wire {
+        self.gate(GateOp::Mul, a, b)
+    }
+
+    /// Sums the wires with a balanced tree of ADD gates
+    pub fn sum(&mut self, wires: &[Wire]) -> Wire {
+        self.reduce(GateOp::Add, wires)
+    }
+
+    /// Multiplies the wires with a balanced tree of MUL gates
+    pub fn product(&mut self, wires: &[Wire]) -> Wire {
+        self.reduce(GateOp::Mul, wires)
+    }
+
+    /// Builds the circuit, the output layer holds `outputs` in order
+    pub fn build(&self, outputs: &[Wire]) -> LayeredCircuit {
+        assert!(!outputs.is_empty(), "circuit needs at least one output");
+        let num_layers = outputs
+            .iter()
+            .map(|wire| self.depths[wire.0])
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        // wires needed at every layer, from the output layer down
+        let mut needed = vec![outputs.to_vec()];
+        for layer in (1..num_layers).rev() {
+            let mut wires = vec![];
+            for wire in needed.last().unwrap() {
+                let operands = match self.nodes[wire.0] {
+                    Node::Gate(_, operands) if self.depths[wire.0] == layer + 1 => operands,
+                    _ => [*wire, self.zero()],
+                };
+                for operand in operands {
+                    if !wires.contains(&operand) {
+                        wires.push(operand);
+                    }
+                }
+            }
+            needed.push(wires);
+        }
+        needed.reverse();
+
+        let mut layers = Vec::with_capacity(num_layers);
+        for (layer, wires) in needed.iter().enumerate() {
+            let positions = if layer == 0 {
+                None
+            } else {
+                Some(
+                    needed[layer - 1]
+                        .iter()
+                        .enumerate()
+                        .map(|(position, wire)| (*wire, position))
+                        .collect::<HashMap<_, _>>(),
+                )
+            };
+            let position = |wire: Wire| match (&positions, &self.nodes[wire.0]) {
+                (Some(positions), _) => positions[&wire],
+                (None, Node::Input(index)) => *index,
+                (None, Node::Gate(..)) => {
+                    unreachable!("gates are not available at the input layer")
+                }
+            };
+
+            let gates = wires
+                .iter()
+                .map(|wire| match &self.nodes[wire.0] {
+                    Node::Gate(op, [a, b]) if self.depths[wire.0] == layer + 1 => {
+                        Gate::new(op.clone(), [position(*a), position(*b)])
+                    }
+                    _ => Gate::new(GateOp::Add, [position(*wire), position(self.zero())]),
+                })
+                .collect();
+            layers.push(Layer::new(gates));
+        }
+
+        LayeredCircuit::new(layers)
+    }
+
+    fn gate(&mut self, op: GateOp, a: Wire, b: Wire) -> Wire {
+        let depth = self.depths[a.0].max(self.depths[b.0]) + 1;
+        self.push(Node::Gate(op, [a, b]), depth)
+    }
+
+    fn reduce(&mut self, op: GateOp, wires: &[Wire]) -> Wire {
+        assert!(!wires.is_empty(), "cannot reduce an empty list of wires");
+        let mut level = wires.to_vec();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => self.gate(op.clone(), *a, *b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        level[0]
+    }
+
+    fn push(&mut self, node: Node, depth: usize) -> Wire {
+        self.nodes.push(node);
+        self.depths.push(depth);
+        Wire(self.nodes.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::CircuitTr;
+    use p3_field::AbstractField;
+    use p3_goldilocks::Goldilocks as F;
+
+    #[test]
+    fn test_builder_relays_values_across_layers() {
+        // out_0 = (a * b) * c + a, out_1 = b
+        let mut builder = CircuitBuilder::new();
+        let [a, b, c] = builder.inputs(3).try_into().unwrap();
+        let ab = builder.mul(a, b);
+        let abc = builder.mul(ab, c);
+        let out = builder.add(abc, a);
+        let circuit = builder.build(&[out, b]);
+
+        assert_eq!(circuit.layers.len(), 3);
+        assert_eq!(builder.num_inputs(), 4);
+
+        let input = [0, 2, 3, 5].map(F::from_canonical_u32);
+        let trace = circuit.excecute(&input);
+        assert_eq!(
+            trace.layers.last().unwrap(),
+            &vec![F::from_canonical_u32(32), F::from_canonical_u32(3)]
+        );
+    }
+
+    #[test]
+    fn test_sum_and_product_trees() {
+        let mut builder = CircuitBuilder::new();
+        let inputs = builder.inputs(5);
+        let sum = builder.sum(&inputs);
+        let product = builder.product(&inputs);
+        let circuit = builder.build(&[sum, product]);
+
+        // balanced trees over 5 values have depth 3
+        assert_eq!(circuit.layers.len(), 3);
+
+        let input = [0, 1, 2, 3, 4, 5].map(F::from_canonical_u32);
+        let trace = circuit.excecute(&input);
+        assert_eq!(
+            trace.layers.last().unwrap(),
+            &vec![F::from_canonical_u32(15), F::from_canonical_u32(120)]
+        );
+    }
+}
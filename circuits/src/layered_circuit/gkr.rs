@@ -1,13 +1,13 @@
 //! Hold circuit extension for the GKR protocol
 use p3_field::{ExtensionField, Field};
-use poly::mle::MultilinearPoly;
+use poly::sparse_mle::SparseMultilinearPoly;
 
 use super::LayeredCircuit;
 use crate::{
     interface::GKRLayeredCircuitTr,
     layered_circuit::{
         primitives::GateOp,
-        utils::{compute_num_vars, get_gate_properties, mle_vec_to_poly},
+        utils::{compute_num_vars, get_gate_properties, mle_vec_to_sparse_poly},
     },
 };
 
@@ -16,7 +16,7 @@ where
     F: Field + Copy + std::ops::Add<Output = F> + std::ops::Mul<Output = F>,
     E: ExtensionField<F>,
 {
-    type AddAndMulMLE = (MultilinearPoly<F, E>, MultilinearPoly<F, E>);
+    type AddAndMulMLE = (SparseMultilinearPoly<F, E>, SparseMultilinearPoly<F, E>);
 
     fn add_and_mul_mle(&self, layer_index: usize) -> Self::AddAndMulMLE {
         assert!(
@@ -44,8 +44,8 @@ where
 
         let mle_num_var = compute_num_vars(layer_index, self.layers.len() - 1);
 
-        let add_mle = mle_vec_to_poly(&add_usize_vec, mle_num_var);
-        let mul_mle = mle_vec_to_poly(&mul_usize_vec, mle_num_var);
+        let add_mle = mle_vec_to_sparse_poly(&add_usize_vec, mle_num_var);
+        let mul_mle = mle_vec_to_sparse_poly(&mul_usize_vec, mle_num_var);
 
         (add_mle, mul_mle)
     }
@@ -83,16 +83,8 @@ mod test {
             BinomialExtensionField<F, 2>,
         >>::add_and_mul_mle(&circuit, circuit.layers.len() - 1);
 
-        let is_zero = mul_mle
-            .evaluations
-            .iter()
-            .all(|x| *x == Fields::Base(F::from_canonical_u32(0)));
-        assert!(is_zero);
-        let is_zero = add_mle
-            .evaluations
-            .iter()
-            .all(|x| *x == Fields::Base(F::from_canonical_u32(0)));
-        assert!(!is_zero);
+        assert_eq!(mul_mle.nnz(), 0);
+        assert_eq!(add_mle.nnz(), 1);
 
         assert_eq!(
             add_mle.evaluate(&[
@@ -166,20 +158,10 @@ mod test {
         >>::add_and_mul_mle(&circuit, layer_index);
 
         // There is one mul gate in layer 1, the mul mle should be non-zero
-        assert!(
-            mul_mle
-                .evaluations
-                .iter()
-                .any(|&x| x != Fields::Base(F::from_canonical_u32(0)))
-        );
+        assert_eq!(mul_mle.nnz(), 1);
 
         // There is one add gate in layer 1, the add mle should be non-zero
-        assert!(
-            add_mle
-                .evaluations
-                .iter()
-                .any(|&x| x != Fields::Base(F::from_canonical_u32(0)))
-        );
+        assert_eq!(add_mle.nnz(), 1);
 
         // This should be the number of variables for the MLE (2 + 2 + 1 = 5 for this case)
         // The number of variables is likely log2(gates in current layer) + log2(gates in next layer) + log2(gates in previous layer)
@@ -1,13 +1,16 @@
 //! An implemenation for a the Layered arithementic circuit, built targetting GKR, Libra protocol
 //! This is not an IR for Virgo at this moment.
+pub mod builder;
 pub mod circuit;
 pub mod gkr;
 pub mod libra;
 pub mod primitives;
 pub mod utils;
 
+use p3_field::{ExtensionField, Field};
 use primitives::{Gate, GateOp, Layer};
 use rand;
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
 
 /// Layered Circuit, a layered sturcture of gate composites
 #[derive(Debug, Clone)]
@@ -81,6 +84,12 @@ impl LayeredCircuit {
     }
 }
 
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for LayeredCircuit {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.layers.absorb_into(transcript);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,4 +124,25 @@ mod tests {
 
         // Gate types are random, so we don't check them specifically
     }
+
+    #[test]
+    fn test_circuit_binds_to_transcript() {
+        use p3_field::extension::BinomialExtensionField;
+        use p3_goldilocks::Goldilocks as F;
+        use transcript::KeccakChallenger64;
+
+        type E = BinomialExtensionField<F, 2>;
+        let challenge = |circuit: &LayeredCircuit| {
+            let mut transcript = Transcript::<F, E, KeccakChallenger64<F>>::new();
+            transcript.absorb(b"circuit", circuit);
+            transcript.challenge(b"challenge")
+        };
+
+        let circuit = LayeredCircuit::random(3);
+        assert_eq!(challenge(&circuit), challenge(&circuit.clone()));
+
+        let mut rewired = circuit.clone();
+        rewired.layers[1].gates[0].op = GateOp::Add;
+        assert_ne!(challenge(&circuit), challenge(&rewired));
+    }
 }
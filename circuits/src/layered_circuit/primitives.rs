@@ -1,5 +1,8 @@
 //! Holds primitives and core types used across the layered circuit
 
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
 /// Enum declaring the gate ops this circuit works with
 #[derive(Debug, Clone)]
 pub enum GateOp {
@@ -49,3 +52,21 @@ impl<F> Evaluation<F> {
         Self { layers }
     }
 }
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for Gate {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        let op: usize = match self.op {
+            GateOp::Add => 0,
+            GateOp::Mul => 1,
+        };
+        op.absorb_into(transcript);
+        self.inputs[0].absorb_into(transcript);
+        self.inputs[1].absorb_into(transcript);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for Layer {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.gates.absorb_into(transcript);
+    }
+}
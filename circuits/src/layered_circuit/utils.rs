@@ -1,7 +1,7 @@
 //! Util functions for the layered circuits
 
 use p3_field::{ExtensionField, Field};
-use poly::{Fields, mle::MultilinearPoly};
+use poly::{Fields, mle::MultilinearPoly, sparse_mle::SparseMultilinearPoly};
 
 /// Function calculates the number of variable of the mle for a given circuit layer
 pub fn compute_num_vars(layer_index: usize, max_layer: usize) -> usize {
@@ -33,16 +33,64 @@ pub fn get_gate_properties(a: usize, b: usize, c: usize, layer_index: usize) ->
     a_shifted | b_shifted | c
 }
 
-/// Obtain the Multlinear poly for the MLE
+/// Obtain the Multlinear poly for the MLE, an indicator of the gates' indices
 pub fn mle_vec_to_poly<F: Field, E: ExtensionField<F>>(
     vec: &[usize],
     num_vars: usize,
 ) -> MultilinearPoly<F, E> {
-    let mut mle_poly = MultilinearPoly::zero(num_vars);
+    let mut evaluations = vec![F::zero(); 1 << num_vars];
 
     for i in vec {
-        mle_poly.evaluations[*i] = Fields::Base(F::one());
+        evaluations[*i] = F::one();
     }
 
-    mle_poly
+    MultilinearPoly::from_base(num_vars, evaluations)
+}
+
+/// Obtain the sparse Multilinear poly for the MLE, storing only the gates' indices.
+/// Like `mle_vec_to_poly` it is an indicator, a repeated index is one rather than summed.
+pub fn mle_vec_to_sparse_poly<F: Field, E: ExtensionField<F>>(
+    vec: &[usize],
+    num_vars: usize,
+) -> SparseMultilinearPoly<F, E> {
+    let mut indices = vec.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+
+    SparseMultilinearPoly::new(
+        num_vars,
+        indices
+            .into_iter()
+            .map(|i| (i, Fields::Base(F::one())))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks as F;
+    use poly::{Fields, MultilinearExtension};
+
+    use super::{mle_vec_to_poly, mle_vec_to_sparse_poly};
+
+    type E = BinomialExtensionField<F, 2>;
+
+    #[test]
+    fn test_sparse_wiring_matches_dense() {
+        // the repeated gate index stays an indicator in both forms
+        let gates = [1, 9, 30, 9];
+        let dense = mle_vec_to_poly::<F, E>(&gates, 5);
+        let sparse = mle_vec_to_sparse_poly::<F, E>(&gates, 5);
+        assert_eq!(sparse.nnz(), 3);
+        assert_eq!(sparse.to_dense(), dense);
+
+        let point = (0..5)
+            .map(|i| Fields::Extension(E::from_canonical_u32(7 * i + 3)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sparse.evaluate(&point).to_extension_field(),
+            dense.evaluate(&point).to_extension_field()
+        );
+    }
 }
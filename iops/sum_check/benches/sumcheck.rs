@@ -0,0 +1,63 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use p3_field::extension::BinomialExtensionField;
+use p3_mersenne_31::Mersenne31;
+use poly::{Fields, MultilinearExtension, mle::MultilinearPoly, utils::product_poly};
+use rand::{Rng, thread_rng};
+use sum_check::{SumCheck, interface::SumCheckInterface};
+use transcript::Transcript;
+
+type F = Mersenne31;
+type E = BinomialExtensionField<Mersenne31, 3>;
+
+/// Suffix used to tell serial and `parallel` feature runs apart
+const MODE: &str = if cfg!(feature = "parallel") {
+    "parallel"
+} else {
+    "serial"
+};
+
+fn random_mle(n_vars: usize) -> MultilinearPoly<F, E> {
+    let mut rng = thread_rng();
+    MultilinearPoly::new_from_vec(
+        n_vars,
+        (0..1 << n_vars)
+            .map(|_| Fields::Base(F::new(rng.gen_range(0..(1 << 30)))))
+            .collect(),
+    )
+}
+
+fn bench_sumcheck_prove(c: &mut Criterion) {
+    for n_vars in [12, 16, 20] {
+        c.bench_function(&format!("sumcheck_prove_mle_{n_vars}_vars_{MODE}"), |b| {
+            b.iter_batched(
+                || {
+                    let poly = random_mle(n_vars);
+                    (poly.sum_over_hypercube(), poly)
+                },
+                |(claimed_sum, poly)| {
+                    SumCheck::prove(claimed_sum, poly, &mut Transcript::init()).unwrap()
+                },
+                BatchSize::LargeInput,
+            );
+        });
+
+        c.bench_function(
+            &format!("sumcheck_prove_product_vpoly_{n_vars}_vars_{MODE}"),
+            |b| {
+                b.iter_batched(
+                    || {
+                        let poly = product_poly(vec![random_mle(n_vars), random_mle(n_vars)]);
+                        (poly.sum_over_hypercube(), poly)
+                    },
+                    |(claimed_sum, poly)| {
+                        SumCheck::prove(claimed_sum, poly, &mut Transcript::init()).unwrap()
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_sumcheck_prove);
+criterion_main!(benches);
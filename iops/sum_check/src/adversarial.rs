@@ -0,0 +1,292 @@
+//! Adversarial prover harness for sumcheck soundness.
+//! Tampers with honest `SumCheckProof`s and replays them through the verifiers, every
+//! tampered proof must be rejected. The partial verifiers have no oracle check, so they
+//! reject by returning an error or by reducing the proof to a different final claim.
+use std::fmt::Debug;
+
+use p3_field::{ExtensionField, Field, PrimeField32};
+use poly::{Fields, mle::MultilinearPoly};
+use transcript::{KeccakChallenger32, Transcript, TranscriptChallenger};
+
+use crate::{
+    SumCheck,
+    interface::SumCheckInterface,
+    primitives::SumCheckProof,
+    sumcheckable::Sumcheckable,
+    univariate_skip::{UnivariateSkip, UnivariateSkippable},
+};
+
+/// A tampering of a sumcheck proof
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofMutation {
+    /// Adds one to an evaluation of a round polynomial
+    FlipEvaluation { round: usize, index: usize },
+    /// Removes a round polynomial
+    DropRound(usize),
+    /// Adds one to the claimed sum
+    AlterClaimedSum,
+    /// Swaps two round polynomials
+    SwapRounds(usize, usize),
+    /// Appends an evaluation to a round polynomial, raising its degree
+    ExtendRound(usize),
+}
+
+impl ProofMutation {
+    /// Every mutation applicable to `proof`
+    pub fn all<F: Field, E: ExtensionField<F>>(proof: &SumCheckProof<F, E>) -> Vec<Self> {
+        let rounds = &proof.round_polynomials;
+        let mut mutations = vec![Self::AlterClaimedSum];
+        for (round, round_poly) in rounds.iter().enumerate() {
+            mutations
+                .extend((0..round_poly.len()).map(|index| Self::FlipEvaluation { round, index }));
+            mutations.push(Self::DropRound(round));
+            mutations.push(Self::ExtendRound(round));
+            mutations.extend(
+                (round + 1..rounds.len())
+                    .filter(|other| rounds[*other] != *round_poly)
+                    .map(|other| Self::SwapRounds(round, other)),
+            );
+        }
+        mutations
+    }
+
+    /// Returns a tampered copy of `proof`
+    pub fn apply<F: Field, E: ExtensionField<F>>(
+        &self,
+        proof: &SumCheckProof<F, E>,
+    ) -> SumCheckProof<F, E> {
+        let one = Fields::Base(F::one());
+        let mut proof = proof.clone();
+        match self {
+            Self::FlipEvaluation { round, index } => {
+                proof.round_polynomials[*round].values_mut()[*index] += one;
+            }
+            Self::DropRound(round) => {
+                proof.round_polynomials.remove(*round);
+            }
+            Self::AlterClaimedSum => proof.claimed_sum += one,
+            Self::SwapRounds(a, b) => proof.round_polynomials.swap(*a, *b),
+            Self::ExtendRound(round) => proof.round_polynomials[*round].values_mut().push(one),
+        }
+        proof
+    }
+}
+
+/// Asserts that `proof` verifies for `polynomial` and that every mutation of it is rejected
+pub fn assert_rejects_mutations<F, E, T>(polynomial: &T, proof: &SumCheckProof<F, E>)
+where
+    F: PrimeField32,
+    E: ExtensionField<F>,
+    T: Sumcheckable<F, E> + Clone,
+{
+    assert_rejects_mutations_with::<F, E, T, KeccakChallenger32<F>>(polynomial, proof)
+}
+
+/// Same as `assert_rejects_mutations`, verifying with transcripts over the challenger `C`
+pub fn assert_rejects_mutations_with<F, E, T, C>(polynomial: &T, proof: &SumCheckProof<F, E>)
+where
+    F: Field,
+    E: ExtensionField<F>,
+    T: Sumcheckable<F, E> + Clone,
+    C: TranscriptChallenger<F>,
+{
+    assert_rejects_with(proof, |proof| {
+        SumCheck::<F, E, T, C>::verify(polynomial, proof, &mut Transcript::new())
+    });
+}
+
+/// Asserts that `SumCheck::verify_partial` accepts `proof` and that every mutation of it
+/// is rejected or reduced to a different final claim
+pub fn assert_partial_rejects_mutations<F, E, C>(
+    proof: &SumCheckProof<F, E>,
+    num_rounds: usize,
+    max_degree: usize,
+) where
+    F: Field,
+    E: ExtensionField<F>,
+    C: TranscriptChallenger<F>,
+{
+    assert_rejects_with(proof, |proof| {
+        SumCheck::<F, E, MultilinearPoly<F, E>, C>::verify_partial(
+            proof,
+            num_rounds,
+            max_degree,
+            &mut Transcript::new(),
+        )
+    });
+}
+
+/// Asserts that `SumCheck::verify_with_skip` accepts `proof` and rejects every mutation of it
+pub fn assert_skip_rejects_mutations<F, E, T, C>(
+    polynomial: &T,
+    proof: &SumCheckProof<F, E>,
+    skip: &UnivariateSkip<F, E>,
+) where
+    F: Field,
+    E: ExtensionField<F>,
+    T: UnivariateSkippable<F, E> + Clone,
+    C: TranscriptChallenger<F>,
+{
+    assert_rejects_with(proof, |proof| {
+        SumCheck::<F, E, T, C>::verify_with_skip(polynomial, proof, skip, &mut Transcript::new())
+    });
+}
+
+/// Asserts that `verify` accepts `proof` and never returns the same output for a mutation
+fn assert_rejects_with<F, E, R>(
+    proof: &SumCheckProof<F, E>,
+    verify: impl Fn(&SumCheckProof<F, E>) -> Result<R, anyhow::Error>,
+) where
+    F: Field,
+    E: ExtensionField<F>,
+    R: PartialEq + Debug,
+{
+    let honest = verify(proof).expect("honest proof must verify before tampering");
+    for mutation in ProofMutation::all(proof) {
+        let result = verify(&mutation.apply(proof));
+        assert!(
+            result.as_ref().ok() != Some(&honest),
+            "verifier accepted a proof tampered with {mutation:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{
+        Fields, MultilinearExtension, mle::MultilinearPoly, utils::product_poly, vpoly::VPoly,
+    };
+    use transcript::{KeccakChallenger32, Transcript};
+
+    use super::{
+        ProofMutation, assert_partial_rejects_mutations, assert_rejects_mutations,
+        assert_skip_rejects_mutations,
+    };
+    use crate::{
+        SumCheck, interface::SumCheckInterface, padded_sumcheck::PaddedSumcheck,
+        sumcheckable::Sumcheckable, univariate_skip::UnivariateSkip,
+    };
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn to_fields(vals: Vec<u64>) -> Vec<Fields<F, E>> {
+        vals.into_iter()
+            .map(|v| Fields::<F, E>::Base(F::from_canonical_u64(v)))
+            .collect()
+    }
+
+    fn f_abc() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(3, to_fields(vec![0, 0, 0, 3, 0, 0, 2, 5]))
+    }
+
+    fn g_abc() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(3, to_fields(vec![1, 2, 3, 4, 5, 6, 7, 8]))
+    }
+
+    fn honest_proof<T: Sumcheckable<F, E> + Clone>(
+        polynomial: &T,
+        claimed_sum: Fields<F, E>,
+    ) -> crate::primitives::SumCheckProof<F, E> {
+        SumCheck::prove(claimed_sum, polynomial.clone(), &mut Transcript::init()).unwrap()
+    }
+
+    #[test]
+    fn test_mutations_cover_every_round() {
+        let proof = honest_proof(&f_abc(), f_abc().sum_over_hypercube());
+        let mutations = ProofMutation::all(&proof);
+        for round in 0..3 {
+            assert!(mutations.contains(&ProofMutation::DropRound(round)));
+            assert!(mutations.contains(&ProofMutation::ExtendRound(round)));
+        }
+        assert_eq!(
+            ProofMutation::SwapRounds(0, 2)
+                .apply(&proof)
+                .round_polynomials[0],
+            proof.round_polynomials[2]
+        );
+    }
+
+    #[test]
+    fn test_multilinear_poly_rejects_mutations() {
+        let polynomial = f_abc();
+        let proof = honest_proof(&polynomial, polynomial.sum_over_hypercube());
+        assert_rejects_mutations(&polynomial, &proof);
+    }
+
+    #[test]
+    fn test_vpoly_rejects_mutations() {
+        let polynomial = product_poly(vec![f_abc(), g_abc()]);
+        let proof = honest_proof(&polynomial, polynomial.sum_over_hypercube());
+        assert_rejects_mutations(&polynomial, &proof);
+    }
+
+    #[test]
+    fn test_padded_sumcheck_rejects_mutations() {
+        let claimed_sum = f_abc().sum_over_hypercube();
+        let polynomial = PaddedSumcheck::new(f_abc(), 2);
+        let proof = honest_proof(&polynomial, claimed_sum);
+        assert_rejects_mutations(&polynomial, &proof);
+
+        let inner: VPoly<F, E> = product_poly(vec![f_abc(), g_abc()]);
+        let claimed_sum = inner.sum_over_hypercube();
+        let polynomial = PaddedSumcheck::new(inner, 3);
+        let proof = honest_proof(&polynomial, claimed_sum);
+        assert_rejects_mutations(&polynomial, &proof);
+    }
+
+    #[test]
+    fn test_partial_verifiers_reject_mutations() {
+        let polynomial = product_poly(vec![f_abc(), g_abc()]);
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let proof = SumCheck::prove_partial(
+            claimed_sum,
+            &mut polynomial.clone(),
+            &mut Transcript::init(),
+        )
+        .unwrap();
+        assert_partial_rejects_mutations::<F, E, KeccakChallenger32<F>>(&proof, 3, 2);
+
+        // round count and degree are checked against the expected ones, not the proof's
+        let verify_partial = |proof, num_rounds, max_degree| {
+            SumCheck::<F, E, VPoly<F, E>>::verify_partial(
+                proof,
+                num_rounds,
+                max_degree,
+                &mut Transcript::init(),
+            )
+        };
+        assert!(verify_partial(&proof, 3, 2).is_ok());
+        assert!(verify_partial(&proof, 2, 2).is_err());
+        assert!(verify_partial(&proof, 3, 3).is_err());
+        assert!(verify_partial(&ProofMutation::DropRound(2).apply(&proof), 3, 2).is_err());
+
+        let skip = UnivariateSkip::new(2, 2);
+        let proof = SumCheck::prove_with_skip(
+            claimed_sum,
+            polynomial.clone(),
+            &skip,
+            &mut Transcript::init(),
+        )
+        .unwrap();
+        assert_skip_rejects_mutations::<F, E, _, KeccakChallenger32<F>>(&polynomial, &proof, &skip);
+    }
+
+    #[test]
+    fn test_padding_rounds_are_degree_checked() {
+        let claimed_sum = f_abc().sum_over_hypercube();
+        let polynomial = PaddedSumcheck::new(f_abc(), 2);
+        let proof = honest_proof(&polynomial, claimed_sum);
+
+        // a padding round poly of higher degree is rejected outright
+        let tampered = ProofMutation::ExtendRound(4).apply(&proof);
+        let result = SumCheck::verify(&polynomial, &tampered, &mut Transcript::init());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("round polynomial for round 4")
+        );
+    }
+}
@@ -30,10 +30,13 @@ pub trait SumCheckInterface<F: Field, E: ExtensionField<F>> {
         transcript: &mut Self::Transcript,
     ) -> Result<Self::Proof, anyhow::Error>;
 
-    // Partially verifies a sumcheck proof without knowing the initial polynomial
+    // Partially verifies a sumcheck proof of `num_rounds` rounds of degree `max_degree`
+    // round polys without knowing the initial polynomial, returns the final claim
     // For use in GKR
     fn verify_partial(
         proof: &Self::Proof,
+        num_rounds: usize,
+        max_degree: usize,
         transcript: &mut Self::Transcript,
-    ) -> (E, Vec<Fields<F, E>>);
+    ) -> Result<(E, Vec<Fields<F, E>>), anyhow::Error>;
 }
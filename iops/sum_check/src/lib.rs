@@ -1,26 +1,54 @@
 //! This module contains the implementation of the sum check protocol.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod adversarial;
 pub mod interface;
 pub mod padded_sumcheck;
 pub mod primitives;
+pub mod streaming;
 pub mod sumcheckable;
+pub mod univariate_skip;
+pub mod verifier_circuit;
 
 use crate::sumcheckable::Sumcheckable;
 use interface::SumCheckInterface;
-use p3_field::{ExtensionField, Field, PrimeField32};
-use poly::{Fields, utils::barycentric_evaluation};
+use p3_field::{ExtensionField, Field};
+use poly::{Fields, barycentric::BarycentricDomain, univariate::UnivariatePoly};
 use primitives::SumCheckProof;
 use std::marker::PhantomData;
-use transcript::Transcript;
+use transcript::{
+    KeccakChallenger32, Transcript, TranscriptChallenger,
+    channel::{FiatShamirVerifierChannel, ProverChannel, VerifierChannel},
+};
+use univariate_skip::{UnivariateSkip, UnivariateSkippable};
 
-pub struct SumCheck<F: Field, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clone> {
-    _marker: PhantomData<(F, E, T)>,
+/// Transcript label of the claimed sum
+pub const CLAIMED_SUM_LABEL: &[u8] = b"sumcheck_claimed_sum";
+/// Transcript label of a round polynomial
+pub const ROUND_POLY_LABEL: &[u8] = b"sumcheck_round_poly";
+/// Transcript label of a round challenge
+pub const ROUND_CHALLENGE_LABEL: &[u8] = b"sumcheck_round_challenge";
+/// Transcript label of the number of skipped variables
+const SKIP_K_LABEL: &[u8] = b"sumcheck_skip_k";
+/// Transcript label of the univariate skip round polynomial
+const SKIP_ROUND_POLY_LABEL: &[u8] = b"sumcheck_skip_round_poly";
+/// Transcript label of the univariate skip challenge
+const SKIP_CHALLENGE_LABEL: &[u8] = b"sumcheck_skip_challenge";
+
+/// Sumcheck prover and verifier, `C` is the challenger backing the transcript
+pub struct SumCheck<
+    F: Field,
+    E: ExtensionField<F>,
+    T: Sumcheckable<F, E> + Clone,
+    C = KeccakChallenger32<F>,
+> {
+    _marker: PhantomData<(F, E, T, C)>,
 }
 
-impl<F: Field + PrimeField32, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clone>
-    SumCheckInterface<F, E> for SumCheck<F, E, T>
+impl<F: Field, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clone, C: TranscriptChallenger<F>>
+    SumCheckInterface<F, E> for SumCheck<F, E, T, C>
 {
     type Polynomial = T;
-    type Transcript = Transcript<F, E>;
+    type Transcript = Transcript<F, E, C>;
     type Proof = SumCheckProof<F, E>;
 
     fn prove(
@@ -32,9 +60,9 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clon
         polynomial.commit(transcript);
 
         // Append claimed sum to transcript
-        transcript.observe(&[claimed_sum]);
+        transcript.append_message(CLAIMED_SUM_LABEL, &[claimed_sum]);
 
-        SumCheck::<F, E, T>::prove_partial(claimed_sum, &mut polynomial, transcript)
+        SumCheck::<F, E, T, C>::prove_partial(claimed_sum, &mut polynomial, transcript)
     }
 
     fn verify(
@@ -46,15 +74,20 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clon
         polynomial.commit(transcript);
 
         // Appends the claimed sum to the transcript
-        transcript.observe(&[proof.claimed_sum]);
+        transcript.append_message(CLAIMED_SUM_LABEL, &[proof.claimed_sum]);
 
         // Perform round by round verification
-        let (claimed_sum, challenges) = SumCheck::<F, E, T>::verify_partial(proof, transcript);
+        let (claimed_sum, challenges) = Self::verify_partial(
+            proof,
+            polynomial.no_of_rounds(),
+            polynomial.max_var_degree(),
+            transcript,
+        )?;
 
         // Oracle check
-        assert_eq!(
-            claimed_sum,
-            polynomial.eval(&challenges).to_extension_field()
+        anyhow::ensure!(
+            claimed_sum == polynomial.eval(&challenges).to_extension_field(),
+            "oracle check failed"
         );
 
         Ok(true)
@@ -65,15 +98,116 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clon
         polynomial: &mut Self::Polynomial,
         transcript: &mut Self::Transcript,
     ) -> Result<Self::Proof, anyhow::Error> {
-        // Init round polynomials struct
-        let mut round_polynomials = Vec::with_capacity(polynomial.no_of_rounds());
+        SumCheck::<F, E, T, C>::prove_partial_with_channel(claimed_sum, polynomial, transcript)
+    }
+
+    fn verify_partial(
+        proof: &Self::Proof,
+        num_rounds: usize,
+        max_degree: usize,
+        transcript: &mut Self::Transcript,
+    ) -> Result<(E, Vec<Fields<F, E>>), anyhow::Error> {
+        Self::verify_rounds(
+            proof.claimed_sum.to_extension_field(),
+            &proof.round_polynomials,
+            num_rounds,
+            max_degree,
+            transcript,
+        )
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clone, C: TranscriptChallenger<F>>
+    SumCheck<F, E, T, C>
+{
+    /// Partially verifies a sumcheck proof over `num_vars` variables of max degree
+    /// `max_degree`, whose first round is a univariate skip over the first `k` variables
+    pub fn verify_partial_with_skip(
+        proof: &SumCheckProof<F, E>,
+        skip: &UnivariateSkip<F, E>,
+        num_vars: usize,
+        max_degree: usize,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<(E, Vec<Fields<F, E>>), anyhow::Error> {
+        anyhow::ensure!(
+            skip.k() <= num_vars,
+            "cannot skip more variables than the polynomial has"
+        );
+        let skip_poly = proof
+            .round_polynomials
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("missing univariate skip round polynomial"))?;
+        // the skip poly has degree at most max_degree · (2^k - 1), whatever the domain width
+        let round_poly_size = UnivariateSkip::<F, E>::round_poly_size(skip.k(), max_degree);
+        anyhow::ensure!(
+            skip.domain().len() >= round_poly_size,
+            "skip domain is too small for the polynomial degree"
+        );
+        anyhow::ensure!(
+            skip_poly.len() <= round_poly_size,
+            "univariate skip round polynomial has {} coefficients, at most {} are allowed",
+            skip_poly.len(),
+            round_poly_size
+        );
+
+        // the skip poly sums to the claim over the skip domain
+        anyhow::ensure!(
+            proof.claimed_sum.to_extension_field()
+                == skip.skip_domain().iter().fold(E::zero(), |acc, point| acc
+                    + skip_poly.evaluate(point).to_extension_field()),
+            "univariate skip round polynomial does not sum to the claim"
+        );
+
+        transcript.absorb(SKIP_K_LABEL, &skip.k());
+        transcript.append_message(SKIP_ROUND_POLY_LABEL, &skip_poly.coefficients());
+        let challenge = Fields::Extension(transcript.challenge(SKIP_CHALLENGE_LABEL));
+        let claimed_sum = skip_poly.evaluate(&challenge).to_extension_field();
+
+        let (claimed_sum, mut challenges) = Self::verify_rounds(
+            claimed_sum,
+            &proof.round_polynomials[1..],
+            num_vars - skip.k(),
+            max_degree,
+            transcript,
+        )?;
+        challenges.insert(0, challenge);
+
+        Ok((claimed_sum, challenges))
+    }
+
+    /// Round by round verification of the round polys of a proof, returns the final
+    /// claim and the challenges
+    fn verify_rounds(
+        claimed_sum: E,
+        round_polynomials: &[UnivariatePoly<F, E>],
+        num_rounds: usize,
+        max_degree: usize,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<(E, Vec<Fields<F, E>>), anyhow::Error> {
+        anyhow::ensure!(
+            round_polynomials.len() == num_rounds,
+            "expected {num_rounds} round polynomials, got {}",
+            round_polynomials.len()
+        );
+        let messages = round_messages(round_polynomials);
+        let mut channel = FiatShamirVerifierChannel::new(transcript, &messages);
+        Self::verify_partial_with_channel(claimed_sum, num_rounds, max_degree, &mut channel)
+    }
 
+    /// Runs the sumcheck rounds against a verifier over any channel, the verifier
+    /// picks the challenges
+    pub fn prove_partial_with_channel<Ch: ProverChannel<F, E>>(
+        claimed_sum: Fields<F, E>,
+        polynomial: &mut T,
+        channel: &mut Ch,
+    ) -> Result<SumCheckProof<F, E>, anyhow::Error> {
+        let mut round_polynomials = Vec::with_capacity(polynomial.no_of_rounds());
         let mut challenges = vec![];
 
         for _ in 0..polynomial.no_of_rounds() {
             let round_message = polynomial.round_message();
-            transcript.observe(&round_message);
-            let challenge = Fields::Extension(transcript.sample_challenge());
+            channel.send_message(ROUND_POLY_LABEL, &round_message.evaluations())?;
+            let challenge = Fields::Extension(channel.receive_challenge(ROUND_CHALLENGE_LABEL)?);
             polynomial.receive_challenge(&challenge);
             round_polynomials.push(round_message);
             challenges.push(challenge);
@@ -86,37 +220,159 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>, T: Sumcheckable<F, E> + Clon
         ))
     }
 
-    fn verify_partial(
-        proof: &Self::Proof,
-        transcript: &mut Self::Transcript,
-    ) -> (E, Vec<Fields<F, E>>) {
-        let mut claimed_sum = proof.claimed_sum.to_extension_field();
-
-        let mut challenges = vec![];
+    /// Verifies `num_rounds` sumcheck rounds of degree `max_degree` round polys received
+    /// over any channel, returns the final claim and the challenges
+    pub fn verify_partial_with_channel<Ch: VerifierChannel<F, E>>(
+        mut claimed_sum: E,
+        num_rounds: usize,
+        max_degree: usize,
+        channel: &mut Ch,
+    ) -> Result<(E, Vec<Fields<F, E>>), anyhow::Error> {
+        anyhow::ensure!(
+            max_degree > 0,
+            "round polys need at least the evaluations at 0 and 1"
+        );
+        let mut challenges = Vec::with_capacity(num_rounds);
+        // round polys share their degree, so the weights are built once
+        let domain = BarycentricDomain::integers(max_degree + 1);
 
-        // Perform round by round verification
-        for round_poly in &proof.round_polynomials {
-            assert_eq!(
-                claimed_sum,
-                round_poly[0].to_extension_field() + round_poly[1].to_extension_field()
+        for round in 0..num_rounds {
+            let round_poly = channel
+                .receive_message(ROUND_POLY_LABEL)
+                .map(UnivariatePoly::from_evaluations)
+                .ok_or_else(|| anyhow::anyhow!("missing round polynomial for round {round}"))?;
+            anyhow::ensure!(
+                round_poly.len() == max_degree + 1,
+                "round polynomial for round {round} has {} evaluations, expected {}",
+                round_poly.len(),
+                max_degree + 1
+            );
+            anyhow::ensure!(
+                claimed_sum
+                    == (round_poly.evaluate_on(&domain, &Fields::Base(F::zero()))
+                        + round_poly.evaluate_on(&domain, &Fields::Base(F::one())))
+                    .to_extension_field(),
+                "round polynomial for round {round} does not sum to the claim"
             );
-            transcript.observe(round_poly);
-            let challenge = Fields::Extension(transcript.sample_challenge());
-            claimed_sum = barycentric_evaluation(round_poly, &challenge).to_extension_field();
+            let challenge = Fields::Extension(channel.send_challenge(ROUND_CHALLENGE_LABEL));
+            claimed_sum = round_poly
+                .evaluate_on(&domain, &challenge)
+                .to_extension_field();
             challenges.push(challenge);
         }
 
-        (claimed_sum, challenges)
+        Ok((claimed_sum, challenges))
+    }
+}
+
+impl<
+    F: Field,
+    E: ExtensionField<F>,
+    T: UnivariateSkippable<F, E> + Clone,
+    C: TranscriptChallenger<F>,
+> SumCheck<F, E, T, C>
+{
+    /// Generate proof for a polynomial sum over the boolean hypercube, collapsing the
+    /// first `k` variables into a univariate skip round
+    pub fn prove_with_skip(
+        claimed_sum: Fields<F, E>,
+        mut polynomial: T,
+        skip: &UnivariateSkip<F, E>,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<SumCheckProof<F, E>, anyhow::Error> {
+        polynomial.commit(transcript);
+        transcript.append_message(CLAIMED_SUM_LABEL, &[claimed_sum]);
+
+        Self::prove_partial_with_skip(claimed_sum, &mut polynomial, skip, transcript)
+    }
+
+    /// Verify a proof generated with `prove_with_skip`
+    pub fn verify_with_skip(
+        polynomial: &T,
+        proof: &SumCheckProof<F, E>,
+        skip: &UnivariateSkip<F, E>,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<bool, anyhow::Error> {
+        polynomial.commit(transcript);
+        transcript.append_message(CLAIMED_SUM_LABEL, &[proof.claimed_sum]);
+
+        let (claimed_sum, challenges) = Self::verify_partial_with_skip(
+            proof,
+            skip,
+            polynomial.no_of_rounds(),
+            polynomial.max_var_degree(),
+            transcript,
+        )?;
+
+        // Oracle check
+        anyhow::ensure!(
+            claimed_sum == polynomial.skip_eval(skip, &challenges).to_extension_field(),
+            "oracle check failed"
+        );
+
+        Ok(true)
+    }
+
+    /// Generates a sumcheck proof without committing to the initial polynomial,
+    /// the first round is a univariate skip over the first `k` variables
+    pub fn prove_partial_with_skip(
+        claimed_sum: Fields<F, E>,
+        polynomial: &mut T,
+        skip: &UnivariateSkip<F, E>,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<SumCheckProof<F, E>, anyhow::Error> {
+        anyhow::ensure!(
+            skip.k() <= polynomial.no_of_rounds(),
+            "cannot skip more variables than the polynomial has"
+        );
+        anyhow::ensure!(
+            skip.domain().len()
+                >= UnivariateSkip::<F, E>::round_poly_size(skip.k(), polynomial.max_var_degree()),
+            "skip domain is too small for the polynomial degree"
+        );
+
+        transcript.absorb(SKIP_K_LABEL, &skip.k());
+        let round_message = polynomial.skip_round_message(skip);
+        transcript.append_message(SKIP_ROUND_POLY_LABEL, &round_message.coefficients());
+        let challenge = Fields::Extension(transcript.challenge(SKIP_CHALLENGE_LABEL));
+        polynomial.receive_skip_challenge(skip, &challenge);
+
+        let mut proof = Self::prove_partial(claimed_sum, polynomial, transcript)?;
+        proof.round_polynomials.insert(0, round_message);
+        proof.challenges.insert(0, challenge);
+
+        Ok(proof)
     }
 }
 
+/// Round polynomials as channel messages, evaluated over 0..n
+fn round_messages<F: Field, E: ExtensionField<F>>(
+    round_polynomials: &[UnivariatePoly<F, E>],
+) -> Vec<Vec<Fields<F, E>>> {
+    round_polynomials
+        .iter()
+        .map(UnivariatePoly::evaluations)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{SumCheck, SumCheckInterface};
-    use p3_field::extension::BinomialExtensionField;
+    use crate::{SumCheck, SumCheckInterface, sumcheckable::Sumcheckable};
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks;
     use p3_mersenne_31::Mersenne31;
-    use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
-    use transcript::Transcript;
+    use poly::{
+        Fields, MultilinearExtension,
+        expr::Expr,
+        mle::{MultilinearPoly, VariableOrder},
+        sop::SumOfProducts,
+        vpoly::VPoly,
+    };
+    use std::thread;
+    use transcript::{
+        KeccakChallenger64, Transcript, channel::interactive_channel,
+        poseidon2::Poseidon2Challenger,
+    };
 
     type F = Mersenne31;
     type E = BinomialExtensionField<Mersenne31, 3>;
@@ -146,4 +402,210 @@ mod tests {
 
         assert!(verify.unwrap());
     }
+
+    #[test]
+    fn test_sumcheck_lsb_first() {
+        let polynomial = f_abc().with_order(VariableOrder::LsbFirst);
+        let claimed_sum = polynomial.sum_over_hypercube();
+
+        let proof =
+            SumCheck::prove(claimed_sum, polynomial.clone(), &mut Transcript::init()).unwrap();
+        assert!(SumCheck::verify(&polynomial, &proof, &mut Transcript::init()).unwrap());
+
+        // the sum of products prover folds the pairs of the LSB-first layout
+        let mut sop = SumOfProducts::new(3);
+        sop.add_term(Fields::Base(F::one()), vec![polynomial.clone(), polynomial]);
+        let claimed_sum = sop.sum_over_hypercube();
+        let proof = SumCheck::prove(claimed_sum, sop.clone(), &mut Transcript::init()).unwrap();
+        assert!(SumCheck::verify(&sop, &proof, &mut Transcript::init()).unwrap());
+    }
+
+    #[test]
+    fn test_sumcheck_over_goldilocks() {
+        type G = Goldilocks;
+        type GE = BinomialExtensionField<Goldilocks, 2>;
+        type C = KeccakChallenger64<G>;
+
+        let polynomial = MultilinearPoly::<G, GE>::new_from_vec(
+            3,
+            vec![0, 0, 0, 3, 0, 0, 2, 5]
+                .into_iter()
+                .map(|val| Fields::Base(G::from_canonical_u64(val)))
+                .collect(),
+        );
+        let claimed_sum = polynomial.sum_over_hypercube();
+
+        let proof = SumCheck::<G, GE, MultilinearPoly<G, GE>, C>::prove(
+            claimed_sum,
+            polynomial.clone(),
+            &mut Transcript::new(),
+        )
+        .unwrap();
+        let verify = SumCheck::<G, GE, MultilinearPoly<G, GE>, C>::verify(
+            &polynomial,
+            &proof,
+            &mut Transcript::new(),
+        );
+        assert!(verify.unwrap());
+    }
+
+    #[test]
+    fn test_sumcheck_over_poseidon2_transcript() {
+        type P = Poseidon2Challenger;
+
+        let polynomial = f_abc();
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let proof = SumCheck::<F, E, MultilinearPoly<F, E>, P>::prove(
+            claimed_sum,
+            polynomial.clone(),
+            &mut Transcript::new(),
+        )
+        .unwrap();
+        let verify = SumCheck::<F, E, MultilinearPoly<F, E>, P>::verify(
+            &polynomial,
+            &proof,
+            &mut Transcript::new(),
+        );
+        assert!(verify.unwrap());
+
+        // the Keccak transcript derives different challenges
+        let keccak_proof =
+            SumCheck::prove(claimed_sum, polynomial.clone(), &mut Transcript::init()).unwrap();
+        assert_ne!(proof.challenges, keccak_proof.challenges);
+    }
+
+    #[test]
+    fn test_interactive_sumcheck() {
+        let polynomial = f_abc();
+        let claimed_sum = polynomial.sum_over_hypercube();
+
+        let mut next = 0;
+        let (mut prover_channel, mut verifier_channel) = interactive_channel(move || {
+            next += 5;
+            E::from_canonical_u32(next)
+        });
+
+        let mut prover_polynomial = polynomial.clone();
+        let prover = thread::spawn(move || {
+            SumCheck::<F, E, MultilinearPoly<F, E>>::prove_partial_with_channel(
+                claimed_sum,
+                &mut prover_polynomial,
+                &mut prover_channel,
+            )
+            .unwrap()
+        });
+
+        let (claim, challenges) =
+            SumCheck::<F, E, MultilinearPoly<F, E>>::verify_partial_with_channel(
+                claimed_sum.to_extension_field(),
+                polynomial.num_vars(),
+                polynomial.max_degree(),
+                &mut verifier_channel,
+            )
+            .unwrap();
+        assert_eq!(claim, polynomial.evaluate(&challenges).to_extension_field());
+        assert_eq!(prover.join().unwrap().challenges, challenges);
+    }
+
+    #[test]
+    fn test_interactive_sumcheck_rejects_wrong_claim() {
+        let polynomial = f_abc();
+        let wrong_sum = polynomial.sum_over_hypercube() + Fields::Base(F::new(1));
+
+        // an honest prover cannot convince the verifier of a wrong sum
+        let (mut prover_channel, mut verifier_channel) = interactive_channel(E::zero);
+        let mut prover_polynomial = polynomial.clone();
+        let prover = thread::spawn(move || {
+            SumCheck::<F, E, MultilinearPoly<F, E>>::prove_partial_with_channel(
+                wrong_sum,
+                &mut prover_polynomial,
+                &mut prover_channel,
+            )
+        });
+
+        let result = SumCheck::<F, E, MultilinearPoly<F, E>>::verify_partial_with_channel(
+            wrong_sum.to_extension_field(),
+            polynomial.num_vars(),
+            polynomial.max_degree(),
+            &mut verifier_channel,
+        );
+        assert!(result.is_err());
+        // the verifier hangs up after rejecting the first round
+        drop(verifier_channel);
+        let proof = prover.join().unwrap();
+        assert_eq!(
+            proof.unwrap_err().to_string(),
+            "verifier endpoint disconnected"
+        );
+    }
+
+    #[test]
+    fn test_mle_round_messages_match_folding() {
+        fn check<T: Sumcheckable<F, E> + MultilinearExtension<F, E>>(polynomial: T) {
+            let expected = (0..=polynomial.max_var_degree())
+                .map(|t| {
+                    polynomial
+                        .partial_evaluate(&[Fields::Base(F::from_canonical_usize(t))])
+                        .sum_over_hypercube()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(polynomial.round_message().evaluations(), expected);
+        }
+
+        check(f_abc());
+        check(f_abc().with_order(VariableOrder::LsbFirst));
+        // 2 · f · g + f
+        let g = f_abc().with_order(VariableOrder::LsbFirst);
+        let expr =
+            Expr::constant(Fields::Base(F::two())) * Expr::mle(0) * Expr::mle(1) + Expr::mle(0);
+        check(VPoly::new_from_expr(vec![f_abc(), g], expr));
+    }
+
+    #[test]
+    fn test_sum_of_products_sumcheck() {
+        // 3 · f · g + 2 · f · f + 5 · g
+        let g = MultilinearPoly::new_from_vec(
+            3,
+            vec![1, 2, 3, 4, 5, 6, 7, 8]
+                .into_iter()
+                .map(|val| Fields::Base(F::new(val)))
+                .collect(),
+        );
+        let mut polynomial = SumOfProducts::new(3);
+        polynomial.add_term(Fields::Base(F::new(3)), vec![f_abc(), g.clone()]);
+        polynomial.add_term(Fields::Base(F::new(2)), vec![f_abc(), f_abc()]);
+        polynomial.add_term(Fields::Base(F::new(5)), vec![g]);
+
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let mut prover_transcript = Transcript::init();
+        let proof =
+            SumCheck::prove(claimed_sum, polynomial.clone(), &mut prover_transcript).unwrap();
+        assert_eq!(proof.round_polynomials[0].len(), 3);
+
+        // the term by term round message agrees with folding the whole polynomial
+        let expected_round_message = (0..=2)
+            .map(|t| {
+                polynomial
+                    .partial_evaluate(&[Fields::Base(F::new(t))])
+                    .sum_over_hypercube()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            polynomial.round_message().evaluations(),
+            expected_round_message
+        );
+
+        let mut verify_transcript = Transcript::init();
+        let verify = SumCheck::verify(&polynomial, &proof, &mut verify_transcript);
+
+        assert!(verify.unwrap());
+
+        // with every variable fixed the round poly is the constant value left
+        let mut folded = polynomial.clone();
+        for t in 0..3 {
+            folded.receive_challenge(&Fields::Base(F::new(t)));
+        }
+        let value = polynomial.evaluate(&[0, 1, 2].map(|t| Fields::Base(F::new(t))));
+        assert_eq!(folded.round_message().evaluations(), vec![value; 3]);
+    }
 }
@@ -3,36 +3,138 @@ use std::marker::PhantomData;
 
 use crate::Fields;
 use crate::sumcheckable::Sumcheckable;
-use p3_field::{ExtensionField, Field, PrimeField32};
+use p3_field::{ExtensionField, Field};
+use poly::univariate::UnivariatePoly;
+use transcript::{Transcript, TranscriptChallenger};
+
+/// Position of the padding variables relative to the inner ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadLayout {
+    /// f'(x, pad) = f(x) * sel(pad)
+    Trailing,
+    /// f'(pad, x) = sel(pad) * f(x)
+    Leading,
+}
+
+/// Selector multiplied onto the inner polynomial over the padding variables.
+/// Every selector sums to one over the boolean hypercube, so padding keeps the claimed sum.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PadSelector<F: Field, E: ExtensionField<F>> {
+    /// d * e * ..., selects the all-ones point
+    One,
+    /// (1 - d) * (1 - e) * ..., selects the all-zeros point
+    Zero,
+    /// eq(point, (d, e, ...)) for an arbitrary point
+    Eq(Vec<Fields<F, E>>),
+}
+
+impl<F: Field, E: ExtensionField<F>> PadSelector<F, E> {
+    /// The eq point of the selector over `pad_count` variables
+    fn points(&self, pad_count: usize) -> Vec<E> {
+        match self {
+            PadSelector::One => vec![E::one(); pad_count],
+            PadSelector::Zero => vec![E::zero(); pad_count],
+            PadSelector::Eq(point) => {
+                assert_eq!(
+                    point.len(),
+                    pad_count,
+                    "eq point must have one coordinate per padding variable"
+                );
+                point.iter().map(Fields::to_extension_field).collect()
+            }
+        }
+    }
+}
 
 #[derive(Clone)]
 /// Pads a polynomial with extra variables in the following form
 /// f(a, b, c) with pad_count = 2 will give
 /// f'(a, b, c, d, e) = d * e * f(a, b, c)
+/// The padding variables can also lead, and the selector can be any eq point,
+/// see `PadLayout` and `PadSelector`.
 /// see: <https://hackmd.io/@iammadab/BJJiocNfel>
-pub struct PaddedSumcheck<F, E, S> {
+pub struct PaddedSumcheck<F: Field, E: ExtensionField<F>, S> {
     inner: S,
     eval: Option<E>,
     n: usize,
     pad_count: usize,
     curr_round: usize,
+    layout: PadLayout,
+    /// eq point of the selector
+    pad_points: Vec<E>,
+    /// product of the selector factors at the padding challenges received so far
+    pad_scale: E,
+    /// sum of the inner polynomial over the hypercube, needed for leading padding rounds
+    inner_sum: Option<E>,
     _marker: PhantomData<(F, E)>,
 }
 
 impl<F: Field, E: ExtensionField<F>, S: Sumcheckable<F, E>> PaddedSumcheck<F, E, S> {
     pub fn new(inner: S, pad_count: usize) -> Self {
+        Self::with_layout(inner, pad_count, PadLayout::Trailing, PadSelector::One)
+    }
+
+    /// Pads with the given layout and selector
+    pub fn with_layout(
+        inner: S,
+        pad_count: usize,
+        layout: PadLayout,
+        selector: PadSelector<F, E>,
+    ) -> Self {
+        // an inner without variables is its own constant value, the trailing rounds start
+        // from it straight away
+        let n = inner.no_of_rounds();
+        let constant = (n == 0).then(|| inner.eval(&[]).to_extension_field());
+        let inner_sum = (layout == PadLayout::Leading && pad_count > 0).then(|| {
+            constant.unwrap_or_else(|| {
+                let message = inner.round_message();
+                (message.evaluate(&Fields::Base(F::zero()))
+                    + message.evaluate(&Fields::Base(F::one())))
+                .to_extension_field()
+            })
+        });
         Self {
-            n: inner.no_of_rounds(),
+            n,
             pad_count,
             inner,
             curr_round: 1,
-            eval: None,
+            eval: constant,
+            layout,
+            pad_points: selector.points(pad_count),
+            pad_scale: E::one(),
+            inner_sum,
             _marker: PhantomData,
         }
     }
+
+    /// Selector factor of the padding variable `index` at `x`
+    fn pad_factor(&self, index: usize, x: E) -> E {
+        let point = self.pad_points[index];
+        point * x + (E::one() - point) * (E::one() - x)
+    }
+
+    /// Product of the selector factors over a padding point
+    fn selector_eval(&self, pad_point: &[Fields<F, E>]) -> E {
+        pad_point
+            .iter()
+            .enumerate()
+            .map(|(index, x)| self.pad_factor(index, x.to_extension_field()))
+            .product()
+    }
+
+    /// Round message of a padding round: the running claim times the selector factor
+    fn pad_round_message(&self, index: usize, claim: E) -> UnivariatePoly<F, E> {
+        UnivariatePoly::from_evaluations(
+            (0..=self.inner.max_var_degree().max(1))
+                .map(|i| {
+                    Fields::Extension(claim * self.pad_factor(index, E::from_canonical_usize(i)))
+                })
+                .collect(),
+        )
+    }
 }
 
-impl<F: Field + PrimeField32, E: ExtensionField<F>, S: Sumcheckable<F, E>> Sumcheckable<F, E>
+impl<F: Field, E: ExtensionField<F>, S: Sumcheckable<F, E>> Sumcheckable<F, E>
     for PaddedSumcheck<F, E, S>
 {
     fn no_of_rounds(&self) -> usize {
@@ -40,49 +142,75 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>, S: Sumcheckable<F, E>> Sumch
     }
 
     fn max_var_degree(&self) -> usize {
-        self.inner.max_var_degree()
+        // padding variables are linear
+        self.inner.max_var_degree().max(1)
     }
 
     fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
         assert!(point.len() == self.no_of_rounds());
-        self.inner.eval(&point[..self.inner.no_of_rounds()])
-            * point[self.inner.no_of_rounds()..].iter().cloned().product()
+        let (inner_point, pad_point) = match self.layout {
+            PadLayout::Trailing => (&point[..self.n], &point[self.n..]),
+            PadLayout::Leading => (&point[self.pad_count..], &point[..self.pad_count]),
+        };
+        self.inner.eval(inner_point) * Fields::Extension(self.selector_eval(pad_point))
     }
 
-    fn round_message(&self) -> Vec<Fields<F, E>> {
-        if self.curr_round <= self.n {
-            self.inner.round_message()
-        } else {
-            (0..=self.max_var_degree())
-                .map(|i| Fields::Extension(E::from_canonical_usize(i) * self.eval.unwrap()))
-                .collect()
+    fn round_message(&self) -> UnivariatePoly<F, E> {
+        match self.layout {
+            PadLayout::Trailing if self.curr_round <= self.n => self.inner.round_message(),
+            PadLayout::Trailing => self.pad_round_message(
+                self.curr_round - self.n - 1,
+                self.eval.unwrap() * self.pad_scale,
+            ),
+            PadLayout::Leading if self.curr_round <= self.pad_count => self.pad_round_message(
+                self.curr_round - 1,
+                self.inner_sum.unwrap() * self.pad_scale,
+            ),
+            PadLayout::Leading => self.inner.round_message() * Fields::Extension(self.pad_scale),
         }
     }
 
     fn receive_challenge(&mut self, challenge: &Fields<F, E>) {
-        if self.curr_round < self.n {
-            self.inner.receive_challenge(challenge);
-        } else if self.curr_round == self.n {
-            let claimed_sum = self.inner.eval(&[*challenge]);
-            self.eval = Some(claimed_sum.to_extension_field());
-        } else {
-            self.eval = Some(self.eval.unwrap() * challenge.to_extension_field());
+        let pad_index = match self.layout {
+            PadLayout::Trailing => self.curr_round.checked_sub(self.n + 1),
+            PadLayout::Leading => (self.curr_round <= self.pad_count).then(|| self.curr_round - 1),
+        };
+
+        match pad_index {
+            Some(index) => {
+                self.pad_scale *= self.pad_factor(index, challenge.to_extension_field());
+            }
+            None if self.layout == PadLayout::Trailing && self.curr_round == self.n => {
+                let claimed_sum = self.inner.eval(&[*challenge]);
+                self.eval = Some(claimed_sum.to_extension_field());
+            }
+            None => self.inner.receive_challenge(challenge),
         }
 
         self.curr_round += 1;
     }
 
-    fn commit(&self, transcript: &mut transcript::Transcript<F, E>) {
+    fn commit<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
         // commit the inner structure
         self.inner.commit(transcript);
         // commit the pad count
-        transcript.observe_ext_element(&[E::from_canonical_usize(self.pad_count)])
+        transcript.absorb(b"padded_sumcheck_pad_count", &self.pad_count);
+        // commit the layout and the selector
+        transcript.absorb(b"padded_sumcheck_layout", &(self.layout as usize));
+        transcript.absorb(
+            b"padded_sumcheck_selector",
+            &self
+                .pad_points
+                .iter()
+                .map(|point| Fields::Extension(*point))
+                .collect::<Vec<_>>(),
+        );
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use p3_field::{AbstractField, extension::BinomialExtensionField};
     use p3_mersenne_31::Mersenne31 as F;
@@ -96,7 +224,8 @@ mod tests {
     use crate::SumCheckInterface;
     use crate::sumcheckable::Sumcheckable;
 
-    use super::PaddedSumcheck;
+    use super::{PadLayout, PadSelector, PaddedSumcheck};
+    use crate::adversarial::assert_rejects_mutations;
 
     fn to_fields(vals: Vec<u64>) -> Vec<Fields<F, E>> {
         vals.into_iter()
@@ -156,7 +285,7 @@ mod tests {
     #[test]
     fn test_v_poly_padded_sumcheck() {
         // g(a, b, c) = f(a, b, c) * f(a, b, c)
-        let poly = VPoly::new(vec![f_abc(), f_abc()], 2, Rc::new(prod_combined_fn));
+        let poly = VPoly::new(vec![f_abc(), f_abc()], 2, Arc::new(prod_combined_fn));
         // f(a, b, c) = 2ab + 3bc
         // f(1, 2, 3) = 2(1)(2) + 3(2)(3) = 4 + 18 = 22
         assert_eq!(poly.num_vars(), 3);
@@ -181,4 +310,113 @@ mod tests {
 
         assert!(verification_result.unwrap());
     }
+
+    #[test]
+    fn test_padded_v_poly_sumcheck_on_worker_thread() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PaddedSumcheck<F, E, VPoly<F, E>>>();
+
+        let poly = VPoly::new(vec![f_abc(), f_abc()], 2, Arc::new(prod_combined_fn));
+        let claimed_sum = poly.sum_over_hypercube();
+        let padded_poly = PaddedSumcheck::new(poly, 2);
+
+        // prove on a worker thread, verify on the current one
+        let proof = {
+            let padded_poly = padded_poly.clone();
+            std::thread::spawn(move || {
+                let mut prover_transcript = Transcript::init();
+                SumCheck::prove(claimed_sum, padded_poly, &mut prover_transcript).unwrap()
+            })
+            .join()
+            .unwrap()
+        };
+
+        let mut verify_transcript = Transcript::init();
+        let verification_result = SumCheck::verify(&padded_poly, &proof, &mut verify_transcript);
+
+        assert!(verification_result.unwrap());
+    }
+
+    fn selectors() -> Vec<PadSelector<F, E>> {
+        vec![
+            PadSelector::One,
+            PadSelector::Zero,
+            PadSelector::Eq(to_fields(vec![7, 11])),
+        ]
+    }
+
+    #[test]
+    fn test_padding_layouts_keep_the_sum() {
+        let claimed_sum = f_abc().sum_over_hypercube();
+        for layout in [PadLayout::Trailing, PadLayout::Leading] {
+            for selector in selectors() {
+                let padded_poly = PaddedSumcheck::with_layout(f_abc(), 2, layout, selector);
+                let sum = (0..1 << 5).fold(E::zero(), |acc, index: u64| {
+                    let point = to_fields((0..5).rev().map(|bit| (index >> bit) & 1).collect());
+                    acc + padded_poly.eval(&point).to_extension_field()
+                });
+                assert_eq!(sum, claimed_sum.to_extension_field());
+            }
+        }
+    }
+
+    #[test]
+    fn test_leading_padding_eval() {
+        let padded_poly =
+            PaddedSumcheck::with_layout(f_abc(), 2, PadLayout::Leading, PadSelector::Zero);
+        // (1 - d) * (1 - e) * f(a, b, c) with d = 3, e = 4, a = 3, b = 4, c = 2
+        // (-2) * (-3) * 48 = 288
+        assert_eq!(
+            padded_poly.eval(&to_fields(vec![3, 4, 3, 4, 2])),
+            Fields::Extension(E::from_canonical_u64(288))
+        );
+    }
+
+    #[test]
+    fn test_padding_a_constant_inner() {
+        let constant = MultilinearPoly::<F, E>::new_from_vec(0, to_fields(vec![9]));
+        let claimed_sum = constant.sum_over_hypercube();
+        for layout in [PadLayout::Trailing, PadLayout::Leading] {
+            for selector in selectors() {
+                let padded_poly =
+                    PaddedSumcheck::with_layout(constant.clone(), 2, layout, selector);
+                assert_eq!(padded_poly.no_of_rounds(), 2);
+
+                let message = padded_poly.round_message();
+                assert_eq!(
+                    message.evaluate(&Fields::Base(F::zero()))
+                        + message.evaluate(&Fields::Base(F::one())),
+                    claimed_sum
+                );
+
+                let proof =
+                    SumCheck::prove(claimed_sum, padded_poly.clone(), &mut Transcript::init())
+                        .unwrap();
+                assert!(SumCheck::verify(&padded_poly, &proof, &mut Transcript::init()).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_layout_proofs() {
+        let poly = VPoly::new(vec![f_abc(), f_abc()], 2, Arc::new(prod_combined_fn));
+        for layout in [PadLayout::Trailing, PadLayout::Leading] {
+            for selector in selectors() {
+                let claimed_sum = f_abc().sum_over_hypercube();
+                let padded_poly = PaddedSumcheck::with_layout(f_abc(), 2, layout, selector.clone());
+                let proof =
+                    SumCheck::prove(claimed_sum, padded_poly.clone(), &mut Transcript::init())
+                        .unwrap();
+                assert_rejects_mutations(&padded_poly, &proof);
+
+                let claimed_sum = poly.sum_over_hypercube();
+                let padded_poly = PaddedSumcheck::with_layout(poly.clone(), 2, layout, selector);
+                let proof =
+                    SumCheck::prove(claimed_sum, padded_poly.clone(), &mut Transcript::init())
+                        .unwrap();
+                assert_eq!(proof.round_polynomials[0].len(), 3);
+                assert_rejects_mutations(&padded_poly, &proof);
+            }
+        }
+    }
 }
@@ -1,18 +1,20 @@
 //! Module holds  primitives for the sumcheck protocol
 
 use p3_field::{ExtensionField, Field};
-use poly::Fields;
+use poly::{Fields, univariate::UnivariatePoly};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct SumCheckProof<F: Field, E: ExtensionField<F>> {
     pub claimed_sum: Fields<F, E>,
-    pub round_polynomials: Vec<Vec<Fields<F, E>>>,
+    pub round_polynomials: Vec<UnivariatePoly<F, E>>,
     pub challenges: Vec<Fields<F, E>>,
 }
 
 impl<F: Field, E: ExtensionField<F>> SumCheckProof<F, E> {
     pub fn new(
         claimed_sum: Fields<F, E>,
-        round_polynomials: Vec<Vec<Fields<F, E>>>,
+        round_polynomials: Vec<UnivariatePoly<F, E>>,
         challenges: Vec<Fields<F, E>>,
     ) -> Self {
         Self {
@@ -22,3 +24,11 @@ impl<F: Field, E: ExtensionField<F>> SumCheckProof<F, E> {
         }
     }
 }
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for SumCheckProof<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.claimed_sum.absorb_into(transcript);
+        self.round_polynomials.absorb_into(transcript);
+        self.challenges.absorb_into(transcript);
+    }
+}
@@ -0,0 +1,281 @@
+//! Streaming sumcheck prover for multilinear polynomials.
+//! The evaluations are never held in memory: they are regenerated from a generator
+//! on every pass. Following the time-space trade-off of Chiesa et al., rounds are
+//! grouped into stages of `k` variables. Each stage makes a single pass over the
+//! evaluations to fold the already bound variables and sum out the free ones into a
+//! 2^k table, the rounds of the stage are then run on that table.
+//! This takes ceil(n / k) passes and O(2^k) memory.
+use std::marker::PhantomData;
+
+use anyhow::{anyhow, ensure};
+use p3_field::{ExtensionField, Field};
+use poly::{Fields, univariate::UnivariatePoly};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
+use crate::{
+    CLAIMED_SUM_LABEL, ROUND_CHALLENGE_LABEL, ROUND_POLY_LABEL, primitives::SumCheckProof,
+};
+
+/// Multilinear polynomial whose evaluations over the boolean hypercube are streamed
+/// from a generator, in the same order as `MultilinearPoly::evaluations`
+pub struct StreamingMle<F: Field, E: ExtensionField<F>, G> {
+    /// Number of variables
+    num_vars: usize,
+    /// Regenerates the evaluations on every call
+    generator: G,
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F, E, G, I> StreamingMle<F, E, G>
+where
+    F: Field,
+    E: ExtensionField<F>,
+    G: Fn() -> I,
+    I: IntoIterator<Item = Fields<F, E>>,
+{
+    /// Instantiates a `StreamingMle` from a generator of 2^num_vars evaluations
+    pub fn new(num_vars: usize, generator: G) -> Self {
+        Self {
+            num_vars,
+            generator,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of variables
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Starts a new pass over the evaluations
+    pub fn stream(&self) -> I::IntoIter {
+        (self.generator)().into_iter()
+    }
+
+    /// Returns the sum of evaluations over the boolean hypercube
+    pub fn sum_over_hypercube(&self) -> Fields<F, E> {
+        Fields::Extension(
+            self.stream()
+                .fold(E::zero(), |acc, eval| acc + eval.to_extension_field()),
+        )
+    }
+
+    /// Commits the evaluations to the transcript in one pass, matching the
+    /// commitment of the equivalent `MultilinearPoly`
+    pub fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.num_vars.absorb_into(transcript);
+        (1_usize << self.num_vars).absorb_into(transcript);
+        for eval in self.stream() {
+            eval.absorb_into(transcript);
+        }
+    }
+
+    /// Makes a pass over the evaluations, folding the first variables at `challenges`
+    /// and summing out all variables after the next `stage_vars` ones
+    fn stage_table(
+        &self,
+        challenges: &[Fields<F, E>],
+        stage_vars: usize,
+    ) -> Result<Vec<E>, anyhow::Error> {
+        let free_vars = self.num_vars - challenges.len() - stage_vars;
+        let mut table = vec![E::zero(); 1 << stage_vars];
+        let mut evaluations = self.stream();
+
+        for prefix in 0..1 << challenges.len() {
+            let weight = eq_at(challenges, prefix);
+            for entry in table.iter_mut() {
+                let mut block_sum = E::zero();
+                for _ in 0..1 << free_vars {
+                    let eval = evaluations.next().ok_or_else(|| {
+                        anyhow!("stream yielded fewer than 2^num_vars evaluations")
+                    })?;
+                    block_sum += eval.to_extension_field();
+                }
+                *entry += weight * block_sum;
+            }
+        }
+        ensure!(
+            evaluations.next().is_none(),
+            "stream yielded more than 2^num_vars evaluations"
+        );
+
+        Ok(table)
+    }
+}
+
+/// Evaluates eq(r, x) at the hypercube point with index `x`, the first point of `r`
+/// matching the most significant bit
+fn eq_at<F: Field, E: ExtensionField<F>>(r: &[Fields<F, E>], x: usize) -> E {
+    r.iter().enumerate().fold(E::one(), |acc, (i, r_i)| {
+        let r_i = r_i.to_extension_field();
+        if (x >> (r.len() - 1 - i)) & 1 == 1 {
+            acc * r_i
+        } else {
+            acc * (E::one() - r_i)
+        }
+    })
+}
+
+/// Sumcheck prover over a `StreamingMle`, processing `k` variables per pass.
+/// Proofs are standard `SumCheckProof`s accepted by the `SumCheck` verifier for the
+/// equivalent `MultilinearPoly`.
+pub struct StreamingSumCheck<F: Field, E: ExtensionField<F>> {
+    /// Number of variables per stage
+    k: usize,
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F: Field, E: ExtensionField<F>> StreamingSumCheck<F, E> {
+    /// Instantiates a streaming prover keeping 2^k elements in memory
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "stage must cover at least one variable");
+        Self {
+            k,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generate proof for a streamed polynomial sum over the boolean hypercube
+    pub fn prove<G, I, C: TranscriptChallenger<F>>(
+        &self,
+        claimed_sum: Fields<F, E>,
+        polynomial: &StreamingMle<F, E, G>,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<SumCheckProof<F, E>, anyhow::Error>
+    where
+        G: Fn() -> I,
+        I: IntoIterator<Item = Fields<F, E>>,
+    {
+        polynomial.commit_to_transcript(transcript);
+        transcript.append_message(CLAIMED_SUM_LABEL, &[claimed_sum]);
+
+        self.prove_partial(claimed_sum, polynomial, transcript)
+    }
+
+    /// Generates a streaming sumcheck proof without committing to the initial polynomial
+    pub fn prove_partial<G, I, C: TranscriptChallenger<F>>(
+        &self,
+        claimed_sum: Fields<F, E>,
+        polynomial: &StreamingMle<F, E, G>,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<SumCheckProof<F, E>, anyhow::Error>
+    where
+        G: Fn() -> I,
+        I: IntoIterator<Item = Fields<F, E>>,
+    {
+        let num_vars = polynomial.num_vars();
+        let mut round_polynomials = Vec::with_capacity(num_vars);
+        let mut challenges = Vec::with_capacity(num_vars);
+
+        while challenges.len() < num_vars {
+            let stage_vars = self.k.min(num_vars - challenges.len());
+            let mut table = polynomial.stage_table(&challenges, stage_vars)?;
+
+            for _ in 0..stage_vars {
+                let (lower, upper) = table.split_at(table.len() / 2);
+                let round_message = vec![
+                    Fields::Extension(lower.iter().copied().sum()),
+                    Fields::Extension(upper.iter().copied().sum()),
+                ];
+                transcript.append_message(ROUND_POLY_LABEL, &round_message);
+                let challenge = transcript.challenge(ROUND_CHALLENGE_LABEL);
+
+                // fix the most significant variable of the table
+                table = lower
+                    .iter()
+                    .zip(upper)
+                    .map(|(lo, hi)| *lo + challenge * (*hi - *lo))
+                    .collect();
+
+                round_polynomials.push(UnivariatePoly::from_evaluations(round_message));
+                challenges.push(Fields::Extension(challenge));
+            }
+        }
+
+        Ok(SumCheckProof::new(
+            claimed_sum,
+            round_polynomials,
+            challenges,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
+    use transcript::Transcript;
+
+    use super::{StreamingMle, StreamingSumCheck};
+    use crate::{SumCheck, interface::SumCheckInterface};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    const NUM_VARS: usize = 5;
+
+    fn eval_at(index: u32) -> Fields<F, E> {
+        Fields::Base(F::from_canonical_u32(index * index + 3 * index + 1))
+    }
+
+    fn dense_mle() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(NUM_VARS, (0..1 << NUM_VARS).map(eval_at).collect())
+    }
+
+    #[test]
+    fn test_streaming_proof_matches_dense_proof() {
+        let dense = dense_mle();
+        let claimed_sum = dense.sum_over_hypercube();
+
+        let mut dense_transcript = Transcript::init();
+        let dense_proof = SumCheck::<F, E, MultilinearPoly<F, E>>::prove(
+            claimed_sum,
+            dense.clone(),
+            &mut dense_transcript,
+        )
+        .unwrap();
+
+        let streaming = StreamingMle::new(NUM_VARS, || (0..1 << NUM_VARS).map(eval_at));
+        assert_eq!(streaming.sum_over_hypercube(), claimed_sum);
+
+        for k in 1..=NUM_VARS + 1 {
+            let mut prover_transcript = Transcript::init();
+            let proof = StreamingSumCheck::new(k)
+                .prove(claimed_sum, &streaming, &mut prover_transcript)
+                .unwrap();
+            assert_eq!(proof.round_polynomials, dense_proof.round_polynomials);
+            assert_eq!(proof.challenges, dense_proof.challenges);
+
+            let mut verify_transcript = Transcript::init();
+            let verification_result = SumCheck::<F, E, MultilinearPoly<F, E>>::verify(
+                &dense,
+                &proof,
+                &mut verify_transcript,
+            );
+            assert!(verification_result.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_streaming_rejects_wrong_stream_length() {
+        let claimed_sum = dense_mle().sum_over_hypercube();
+        let prover = StreamingSumCheck::new(2);
+
+        let short = StreamingMle::new(NUM_VARS, || (0..(1 << NUM_VARS) - 1).map(eval_at));
+        assert!(
+            prover
+                .prove_partial(claimed_sum, &short, &mut Transcript::init())
+                .is_err()
+        );
+
+        let long = StreamingMle::new(NUM_VARS, || (0..(1 << NUM_VARS) + 1).map(eval_at));
+        assert!(
+            prover
+                .prove_partial(claimed_sum, &long, &mut Transcript::init())
+                .is_err()
+        );
+    }
+}
@@ -1,8 +1,12 @@
-use p3_field::{ExtensionField, Field, PrimeField32};
+use std::ops::Range;
+
+use p3_field::{ExtensionField, Field};
 use poly::mle::MultilinearPoly;
+use poly::sop::SumOfProducts;
+use poly::univariate::UnivariatePoly;
 use poly::vpoly::VPoly;
 use poly::{Fields, MultilinearExtension};
-use transcript::Transcript;
+use transcript::{Transcript, TranscriptChallenger};
 
 /// Trait for structures that you can run the `Sumcheck` protocol over
 pub trait Sumcheckable<F: Field, E: ExtensionField<F>> {
@@ -12,8 +16,8 @@ pub trait Sumcheckable<F: Field, E: ExtensionField<F>> {
     /// Max variable degree (determines the size of the round poly)
     fn max_var_degree(&self) -> usize;
 
-    /// Returns the round message based on current state
-    fn round_message(&self) -> Vec<Fields<F, E>>;
+    /// Returns the round poly based on current state, evaluated at 0..=max_var_degree
+    fn round_message(&self) -> UnivariatePoly<F, E>;
 
     /// Receives challenge and updates state
     fn receive_challenge(&mut self, challenge: &Fields<F, E>);
@@ -22,46 +26,278 @@ pub trait Sumcheckable<F: Field, E: ExtensionField<F>> {
     fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E>;
 
     /// Commit state to some transcript
-    fn commit(&self, transcript: &mut Transcript<F, E>);
+    fn commit<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>);
 }
 
-macro_rules! impl_sumcheckable_for_mle {
-    ($type:ty) => {
-        impl<F, E> Sumcheckable<F, E> for $type
-        where
-            F: Field + PrimeField32,
-            E: ExtensionField<F>,
-            $type: MultilinearExtension<F, E>,
-        {
-            fn no_of_rounds(&self) -> usize {
-                self.num_vars()
-            }
+/// Number of (0, x), (1, x) pairs summed by one task of the parallel round message
+#[cfg(feature = "parallel")]
+const PAIRS_PER_CHUNK: usize = 1 << 10;
 
-            fn max_var_degree(&self) -> usize {
-                self.max_degree()
-            }
+/// Sums the round evaluations of the pairs 0..half, `sum_range` summing a range of them.
+/// Above the parallel threshold the range is split into fixed chunks whose sums are added
+/// in chunk order, so the round message does not depend on the thread schedule.
+fn sum_over_pairs<E: Field>(
+    half: usize,
+    sum_range: impl Fn(Range<usize>) -> Vec<E> + Sync,
+) -> Vec<E> {
+    #[cfg(feature = "parallel")]
+    if half >= poly::PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        let partials = (0..half.div_ceil(PAIRS_PER_CHUNK))
+            .into_par_iter()
+            .map(|chunk| {
+                sum_range(chunk * PAIRS_PER_CHUNK..((chunk + 1) * PAIRS_PER_CHUNK).min(half))
+            })
+            .collect::<Vec<_>>();
+        return partials
+            .into_iter()
+            .reduce(|mut acc, partial| {
+                for (acc, value) in acc.iter_mut().zip(partial) {
+                    *acc += value;
+                }
+                acc
+            })
+            .expect("at least one chunk");
+    }
 
-            fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
-                self.evaluate(point)
-            }
+    sum_range(0..half)
+}
+
+/// Sums the (0, x) and (1, x) evaluations of an MLE over a range of x
+fn mle_pair_sums<F: Field, E: ExtensionField<F>>(
+    poly: &MultilinearPoly<F, E>,
+    range: Range<usize>,
+) -> Vec<E> {
+    let (mut low_sum, mut high_sum) = (E::zero(), E::zero());
+    for i in range {
+        let (low, high) = poly.first_var_pair(i);
+        low_sum += low.to_extension_field();
+        high_sum += high.to_extension_field();
+    }
+    vec![low_sum, high_sum]
+}
+
+/// Sums the combined rows of a VPoly at t = 0..=degree over a range of x, each row at t
+/// extrapolated from the (0, x) and (1, x) evaluations of its MLEs
+fn vpoly_pair_sums<F: Field, E: ExtensionField<F>>(
+    poly: &VPoly<F, E>,
+    range: Range<usize>,
+) -> Vec<E> {
+    let mut sums = vec![E::zero(); poly.max_degree() + 1];
+    let mut diffs = vec![E::zero(); poly.num_mles()];
+    let mut row = vec![Fields::Extension(E::zero()); poly.num_mles()];
+    for i in range {
+        for (k, mle) in poly.mles().iter().enumerate() {
+            let (low, high) = mle.first_var_pair(i);
+            diffs[k] = high.to_extension_field() - low.to_extension_field();
+            row[k] = low;
+        }
 
-            fn commit(&self, transcript: &mut Transcript<F, E>) {
-                self.commit_to_transcript(transcript);
+        for (t, sum) in sums.iter_mut().enumerate() {
+            if t > 0 {
+                for (value, diff) in row.iter_mut().zip(&diffs) {
+                    *value = Fields::Extension(value.to_extension_field() + *diff);
+                }
             }
+            *sum += poly.combine(&row).to_extension_field();
+        }
+    }
+    sums
+}
+
+/// Sums a sum of products at t = 0..=degree over a range of x, term by term, each MLE
+/// extrapolated once per point and shared by every term using it
+fn sop_pair_sums<F: Field, E: ExtensionField<F>>(
+    poly: &SumOfProducts<F, E>,
+    degree: usize,
+    range: Range<usize>,
+) -> Vec<E> {
+    let mut sums = vec![E::zero(); degree + 1];
+    // folded[t][k] holds mle_k(t, x) for the current x
+    let mut folded = vec![vec![E::zero(); poly.mles().len()]; degree + 1];
 
-            fn receive_challenge(&mut self, challenge: &Fields<F, E>) {
-                *self = self.partial_evaluate(&[*challenge]);
+    for i in range {
+        for (k, mle) in poly.mles().iter().enumerate() {
+            let (low, high) = mle.first_var_pair(i);
+            let low = low.to_extension_field();
+            let diff = high.to_extension_field() - low;
+            let mut value = low;
+            for point in folded.iter_mut() {
+                point[k] = value;
+                value += diff;
             }
+        }
 
-            fn round_message(&self) -> Vec<Fields<F, E>> {
-                (0..=self.max_degree())
-                    .map(|p| Fields::Extension(E::from_canonical_usize(p)))
-                    .map(|p| self.partial_evaluate(&[p]).sum_over_hypercube())
-                    .collect()
+        for (sum, values) in sums.iter_mut().zip(&folded) {
+            for (coeff, indices) in poly.terms() {
+                *sum += indices
+                    .iter()
+                    .fold(coeff.to_extension_field(), |acc, index| {
+                        acc * values[*index]
+                    });
             }
         }
-    };
+    }
+    sums
+}
+
+fn from_sums<F: Field, E: ExtensionField<F>>(sums: Vec<E>) -> UnivariatePoly<F, E> {
+    UnivariatePoly::from_evaluations(sums.into_iter().map(Fields::Extension).collect())
+}
+
+impl<F: Field, E: ExtensionField<F>> Sumcheckable<F, E> for MultilinearPoly<F, E> {
+    fn no_of_rounds(&self) -> usize {
+        self.num_vars()
+    }
+
+    fn max_var_degree(&self) -> usize {
+        self.max_degree()
+    }
+
+    fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
+        self.evaluate(point)
+    }
+
+    fn commit<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.commit_to_transcript(transcript);
+    }
+
+    fn receive_challenge(&mut self, challenge: &Fields<F, E>) {
+        self.fix_first_var_in_place(challenge);
+    }
+
+    /// Sums the (0, x) and (1, x) evaluations in a single pass over the table.
+    /// Once every variable is fixed the round poly is the constant value left.
+    fn round_message(&self) -> UnivariatePoly<F, E> {
+        if self.num_vars() == 0 {
+            return UnivariatePoly::from_evaluations(vec![self.get(0); 2]);
+        }
+
+        from_sums(sum_over_pairs(1 << (self.num_vars() - 1), |range| {
+            mle_pair_sums(self, range)
+        }))
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Sumcheckable<F, E> for VPoly<F, E> {
+    fn no_of_rounds(&self) -> usize {
+        self.num_vars()
+    }
+
+    fn max_var_degree(&self) -> usize {
+        self.max_degree()
+    }
+
+    fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
+        self.evaluate(point)
+    }
+
+    fn commit<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.commit_to_transcript(transcript);
+    }
+
+    fn receive_challenge(&mut self, challenge: &Fields<F, E>) {
+        self.fix_first_var_in_place(challenge);
+    }
+
+    /// Combines the MLEs row by row, each row at t extrapolated from the (0, x) and
+    /// (1, x) evaluations, so no table is folded per evaluation point.
+    /// Once every variable is fixed the round poly is the constant value left.
+    fn round_message(&self) -> UnivariatePoly<F, E> {
+        let degree = self.max_var_degree();
+        if self.num_vars() == 0 {
+            return UnivariatePoly::from_evaluations(vec![self.sum_over_hypercube(); degree + 1]);
+        }
+
+        from_sums(sum_over_pairs(1 << (self.num_vars() - 1), |range| {
+            vpoly_pair_sums(self, range)
+        }))
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Sumcheckable<F, E> for SumOfProducts<F, E> {
+    fn no_of_rounds(&self) -> usize {
+        self.num_vars()
+    }
+
+    fn max_var_degree(&self) -> usize {
+        // a round poly needs at least the evaluations at 0 and 1
+        self.max_degree().max(1)
+    }
+
+    fn eval(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
+        self.evaluate(point)
+    }
+
+    fn commit<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.commit_to_transcript(transcript);
+    }
+
+    fn receive_challenge(&mut self, challenge: &Fields<F, E>) {
+        self.fix_first_var_in_place(challenge);
+    }
+
+    /// Computes the round poly term by term, each MLE is folded once per
+    /// evaluation point and the folded value is shared by every term using it.
+    /// Once every variable is fixed the round poly is the constant value left.
+    fn round_message(&self) -> UnivariatePoly<F, E> {
+        let degree = self.max_var_degree();
+        if self.num_vars() == 0 {
+            return UnivariatePoly::from_evaluations(vec![self.sum_over_hypercube(); degree + 1]);
+        }
+        from_sums(sum_over_pairs(1 << (self.num_vars() - 1), |range| {
+            sop_pair_sums(self, degree, range)
+        }))
+    }
 }
 
-impl_sumcheckable_for_mle!(MultilinearPoly<F, E>);
-impl_sumcheckable_for_mle!(VPoly<F, E>);
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{Fields, expr::Expr, mle::MultilinearPoly, sop::SumOfProducts, vpoly::VPoly};
+
+    use super::{Sumcheckable, mle_pair_sums, sop_pair_sums, vpoly_pair_sums};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    #[test]
+    fn test_round_messages_match_serial_sums() {
+        // large enough to take the threaded path when the `parallel` feature is enabled
+        let num_vars = 14;
+        let half = 1 << (num_vars - 1);
+        let mle = |seed: u32| {
+            MultilinearPoly::<F, E>::from_base(
+                num_vars,
+                (0..1 << num_vars)
+                    .map(|i| F::from_canonical_u32(i * seed + 7))
+                    .collect(),
+            )
+        };
+        let serial = |sums: Vec<E>| sums.into_iter().map(Fields::Extension).collect::<Vec<_>>();
+
+        let f = mle(3);
+        assert_eq!(
+            f.round_message().evaluations(),
+            serial(mle_pair_sums(&f, 0..half))
+        );
+
+        let vpoly = VPoly::new_from_expr(vec![mle(3), mle(5)], Expr::mle(0) * Expr::mle(1));
+        assert_eq!(
+            vpoly.round_message().evaluations(),
+            serial(vpoly_pair_sums(&vpoly, 0..half))
+        );
+
+        let mut sop = SumOfProducts::new(num_vars);
+        sop.add_term(Fields::Base(F::two()), vec![mle(3), mle(5)]);
+        sop.add_term(Fields::Base(F::one()), vec![mle(11)]);
+        assert_eq!(
+            sop.round_message().evaluations(),
+            serial(sop_pair_sums(&sop, sop.max_var_degree(), 0..half))
+        );
+        assert_eq!(
+            sop.round_message().evaluations(),
+            sop.round_message().evaluations()
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Univariate skip for the first sumcheck round.
+//! The first `k` variables are collapsed into a single univariate round: their 2^k
+//! hypercube points are identified with a skip domain H, and the prover sends
+//! p(X) = Σ_{x'} g(f̂(X, x')) where f̂(·, x') interpolates f(·, x') over H.
+//! The verifier checks Σ_{h ∈ H} p(h) against the claimed sum, and the remaining
+//! variables are proven with regular rounds.
+use p3_field::{ExtensionField, Field, TwoAdicField};
+use poly::barycentric::BarycentricDomain;
+use poly::mle::MultilinearPoly;
+use poly::sop::SumOfProducts;
+use poly::univariate::UnivariatePoly;
+use poly::vpoly::VPoly;
+use poly::{Fields, MultilinearExtension};
+
+use crate::sumcheckable::Sumcheckable;
+
+/// Univariate skip configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnivariateSkip<F: Field, E: ExtensionField<F>> {
+    /// Number of variables collapsed into the skip round
+    k: usize,
+    /// Evaluation domain of the skip round poly, the first 2^k points form the
+    /// skip domain H and the i'th one stands for the hypercube point with index i
+    domain: Vec<Fields<F, E>>,
+    /// Barycentric weights of the skip domain H, reused by every fold
+    skip_weights: BarycentricDomain<F, E>,
+}
+
+impl<F: Field, E: ExtensionField<F>> UnivariateSkip<F, E> {
+    /// Skips `k` variables of a poly with the given max variable degree,
+    /// the skip domain is {0, 1, ..., 2^k - 1}
+    pub fn new(k: usize, max_var_degree: usize) -> Self {
+        let skip_domain = (0..1 << k).map(F::from_canonical_usize).collect();
+        Self::with_skip_domain(k, skip_domain, max_var_degree)
+    }
+
+    /// Skips `k` variables over a custom skip domain of 2^k distinct points.
+    /// The domain is extended with small integers until it can hold the skip round poly.
+    pub fn with_skip_domain(k: usize, skip_domain: Vec<F>, max_var_degree: usize) -> Self {
+        assert!(k > 0, "univariate skip needs at least one variable");
+        assert_eq!(
+            skip_domain.len(),
+            1 << k,
+            "skip domain must have 2^k points"
+        );
+
+        let domain_size = Self::round_poly_size(k, max_var_degree);
+        let mut domain = skip_domain;
+        let mut next = 0;
+        while domain.len() < domain_size {
+            let point = F::from_canonical_usize(next);
+            if !domain.contains(&point) {
+                domain.push(point);
+            }
+            next += 1;
+        }
+
+        for (i, point) in domain.iter().enumerate() {
+            assert!(
+                !domain[..i].contains(point),
+                "skip domain points must be distinct"
+            );
+        }
+
+        let domain = domain.into_iter().map(Fields::Base).collect::<Vec<_>>();
+        Self {
+            k,
+            skip_weights: BarycentricDomain::new(domain[..1 << k].to_vec()),
+            domain,
+        }
+    }
+
+    /// Number of evaluations of the skip round poly, which has degree d * (2^k - 1)
+    pub fn round_poly_size(k: usize, max_var_degree: usize) -> usize {
+        max_var_degree * ((1 << k) - 1) + 1
+    }
+
+    /// Number of variables collapsed into the skip round
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Evaluation domain of the skip round poly
+    pub fn domain(&self) -> &[Fields<F, E>] {
+        &self.domain
+    }
+
+    /// The skip domain H, identified with the hypercube {0,1}^k
+    pub fn skip_domain(&self) -> &[Fields<F, E>] {
+        &self.domain[..1 << self.k]
+    }
+
+    /// Lagrange weights of the skip domain at a point, used to fold the first `k` variables
+    pub fn lagrange_weights(&self, point: &Fields<F, E>) -> Vec<Fields<F, E>> {
+        self.skip_weights.lagrange_basis(point)
+    }
+}
+
+impl<F: TwoAdicField, E: ExtensionField<F>> UnivariateSkip<F, E> {
+    /// Skips `k` variables over the multiplicative coset shift · <ω> of order 2^k
+    pub fn over_coset(k: usize, shift: F, max_var_degree: usize) -> Self {
+        let generator = F::two_adic_generator(k);
+        let skip_domain = generator
+            .powers()
+            .take(1 << k)
+            .map(|power| shift * power)
+            .collect();
+        Self::with_skip_domain(k, skip_domain, max_var_degree)
+    }
+}
+
+/// Structures that support collapsing their first variables into a univariate skip round
+pub trait UnivariateSkippable<F: Field, E: ExtensionField<F>>: Sumcheckable<F, E> {
+    /// The skip round poly, interpolated from its evaluations over the skip evaluation domain
+    fn skip_round_message(&self, skip: &UnivariateSkip<F, E>) -> UnivariatePoly<F, E>;
+
+    /// Receives the skip challenge, collapsing the first `k` variables
+    fn receive_skip_challenge(&mut self, skip: &UnivariateSkip<F, E>, challenge: &Fields<F, E>);
+
+    /// Eval structure at the skip challenge followed by the remaining point.
+    /// Needed for the `oracle check`
+    fn skip_eval(&self, skip: &UnivariateSkip<F, E>, point: &[Fields<F, E>]) -> Fields<F, E>;
+}
+
+macro_rules! impl_univariate_skippable_for_mle {
+    ($type:ty) => {
+        impl<F, E> UnivariateSkippable<F, E> for $type
+        where
+            F: Field,
+            E: ExtensionField<F>,
+            $type: MultilinearExtension<F, E> + Sumcheckable<F, E>,
+        {
+            /// Interpolates over the first `round_poly_size` points of the domain, enough
+            /// for the degree of the skip poly however wide the domain is
+            fn skip_round_message(&self, skip: &UnivariateSkip<F, E>) -> UnivariatePoly<F, E> {
+                let domain = &skip.domain()
+                    [..UnivariateSkip::<F, E>::round_poly_size(skip.k(), self.max_var_degree())];
+                let evaluations = domain
+                    .iter()
+                    .map(|point| {
+                        self.fold_first_vars(&skip.lagrange_weights(point))
+                            .sum_over_hypercube()
+                    })
+                    .collect::<Vec<_>>();
+                UnivariatePoly::interpolate(domain, &evaluations)
+            }
+
+            fn receive_skip_challenge(
+                &mut self,
+                skip: &UnivariateSkip<F, E>,
+                challenge: &Fields<F, E>,
+            ) {
+                *self = self.fold_first_vars(&skip.lagrange_weights(challenge));
+            }
+
+            fn skip_eval(
+                &self,
+                skip: &UnivariateSkip<F, E>,
+                point: &[Fields<F, E>],
+            ) -> Fields<F, E> {
+                self.fold_first_vars(&skip.lagrange_weights(&point[0]))
+                    .evaluate(&point[1..])
+            }
+        }
+    };
+}
+
+impl_univariate_skippable_for_mle!(MultilinearPoly<F, E>);
+impl_univariate_skippable_for_mle!(VPoly<F, E>);
+impl_univariate_skippable_for_mle!(SumOfProducts<F, E>);
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::utils::product_poly;
+    use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
+    use transcript::{KeccakChallenger32, Transcript};
+
+    use super::UnivariateSkip;
+    use crate::SumCheck;
+    use crate::adversarial::assert_skip_rejects_mutations;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn to_fields(vals: Vec<u64>) -> Vec<Fields<F, E>> {
+        vals.into_iter()
+            .map(|v| Fields::<F, E>::Base(F::from_canonical_u64(v)))
+            .collect()
+    }
+
+    fn f_abcd() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(
+            4,
+            to_fields(vec![0, 0, 0, 3, 0, 0, 2, 5, 1, 4, 7, 9, 2, 8, 6, 3]),
+        )
+    }
+
+    fn g_abcd() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(4, to_fields((1..=16).collect()))
+    }
+
+    #[test]
+    fn test_skip_domain() {
+        let skip = UnivariateSkip::<F, E>::new(2, 2);
+        // degree 2 * (4 - 1) = 6 needs 7 evaluations
+        assert_eq!(skip.domain().len(), 7);
+        assert_eq!(skip.skip_domain(), to_fields(vec![0, 1, 2, 3]).as_slice());
+
+        // the extension skips points already in the custom skip domain
+        let skip = UnivariateSkip::<F, E>::with_skip_domain(
+            1,
+            vec![F::from_canonical_u64(1), F::from_canonical_u64(3)],
+            2,
+        );
+        assert_eq!(skip.domain(), to_fields(vec![1, 3, 0]).as_slice());
+    }
+
+    #[test]
+    fn test_skip_round_sums_to_claim() {
+        let poly = product_poly(vec![f_abcd(), g_abcd()]);
+        let skip = UnivariateSkip::new(2, 2);
+        let message = <_ as super::UnivariateSkippable<F, E>>::skip_round_message(&poly, &skip);
+        let sum = skip
+            .skip_domain()
+            .iter()
+            .fold(Fields::Base(F::zero()), |acc, point| {
+                acc + message.evaluate(point)
+            });
+        assert_eq!(
+            sum.to_extension_field(),
+            poly.sum_over_hypercube().to_extension_field()
+        );
+    }
+
+    #[test]
+    fn test_univariate_skip_sumcheck() {
+        for k in 1..=3 {
+            let poly = product_poly(vec![f_abcd(), g_abcd()]);
+            let claimed_sum = poly.sum_over_hypercube();
+            let skip = UnivariateSkip::new(k, 2);
+
+            let mut prover_transcript = Transcript::init();
+            let proof =
+                SumCheck::prove_with_skip(claimed_sum, poly.clone(), &skip, &mut prover_transcript)
+                    .unwrap();
+            assert_eq!(proof.round_polynomials.len(), 4 - k + 1);
+            assert_eq!(proof.round_polynomials[0].len(), skip.domain().len());
+
+            let mut verify_transcript = Transcript::init();
+            let verification_result =
+                SumCheck::verify_with_skip(&poly, &proof, &skip, &mut verify_transcript);
+            assert!(verification_result.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_univariate_skip_rejects_forged_proofs() {
+        let poly = product_poly(vec![f_abcd(), g_abcd()]);
+        let claimed_sum = poly.sum_over_hypercube();
+        let skip = UnivariateSkip::new(2, 2);
+        let proof =
+            SumCheck::prove_with_skip(claimed_sum, poly.clone(), &skip, &mut Transcript::init())
+                .unwrap();
+        let verify =
+            |proof| SumCheck::verify_with_skip(&poly, proof, &skip, &mut Transcript::init());
+
+        let mut empty = proof.clone();
+        empty.round_polynomials.clear();
+        assert!(verify(&empty).is_err());
+
+        let mut oversized = proof.clone();
+        oversized.round_polynomials[0]
+            .values_mut()
+            .push(Fields::Base(F::one()));
+        assert!(verify(&oversized).is_err());
+
+        let mut wrong_sum = proof;
+        wrong_sum.claimed_sum += Fields::Base(F::one());
+        assert!(verify(&wrong_sum).is_err());
+    }
+
+    #[test]
+    fn test_wide_skip_domain_bounds_the_skip_degree() {
+        // a domain built for degree 4 holds 13 points, the product poly needs 7
+        let poly = product_poly(vec![f_abcd(), g_abcd()]);
+        let claimed_sum = poly.sum_over_hypercube();
+        let skip = UnivariateSkip::new(2, 4);
+        assert_eq!(skip.domain().len(), 13);
+        let proof =
+            SumCheck::prove_with_skip(claimed_sum, poly.clone(), &skip, &mut Transcript::init())
+                .unwrap();
+        assert_eq!(proof.round_polynomials[0].len(), 7);
+        assert_skip_rejects_mutations::<F, E, _, KeccakChallenger32<F>>(&poly, &proof, &skip);
+
+        // adding x^3 · x(x - 1)(x - 2)(x - 3) keeps the sum over the skip domain {0, 1, 2, 3}
+        // but raises the skip poly to degree 7
+        let mut forged = proof;
+        let coefficients = forged.round_polynomials[0].values_mut();
+        coefficients.push(Fields::Base(F::zero()));
+        let (six, eleven) = (F::from_canonical_u32(6), F::from_canonical_u32(11));
+        for (i, coeff) in [(4, -six), (5, eleven), (6, -six), (7, F::one())] {
+            coefficients[i] += Fields::Base(coeff);
+        }
+        let error =
+            SumCheck::verify_with_skip(&poly, &forged, &skip, &mut Transcript::init()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "univariate skip round polynomial has 8 coefficients, at most 7 are allowed"
+        );
+    }
+
+    #[test]
+    fn test_univariate_skip_sumcheck_over_coset() {
+        type F = BabyBear;
+        type E = BinomialExtensionField<BabyBear, 4>;
+
+        let poly = MultilinearPoly::<F, E>::new_from_vec(
+            4,
+            (0..16)
+                .map(|val| Fields::Base(F::from_canonical_u32(val * val + 1)))
+                .collect(),
+        );
+        let claimed_sum = poly.sum_over_hypercube();
+        let skip = UnivariateSkip::over_coset(3, F::generator(), 1);
+        assert_eq!(skip.domain().len(), 8);
+
+        let mut prover_transcript = Transcript::init();
+        let proof =
+            SumCheck::prove_with_skip(claimed_sum, poly.clone(), &skip, &mut prover_transcript)
+                .unwrap();
+
+        let mut verify_transcript = Transcript::init();
+        let verification_result =
+            SumCheck::verify_with_skip(&poly, &proof, &skip, &mut verify_transcript);
+        assert!(verification_result.unwrap());
+    }
+}
@@ -0,0 +1,353 @@
+//! Sumcheck verifier expressed as a `LayeredCircuit`, for recursion and aggregation.
+//! For every round the circuit outputs `claim - (p(0) + p(1))`, which is zero for an
+//! accepting proof, and it outputs the final claim p_n(r_n) last. Round polys are
+//! evaluated with the Lagrange basis over {0, ..., d}, so the circuit needs no inversions.
+//! Challenges and constants are supplied as circuit inputs, laid out as:
+//! zero, -1, -m for m in 1..=d, lagrange weights w_0..w_d, claimed sum, round polys,
+//! challenges.
+//! Gates have no constants, so the constant inputs are pinned by the circuit: after the
+//! round checks it outputs `zero`, `-m - (-(m-1)) + 1` for every point and
+//! `w_j · Π_{m ≠ j} (j - m) - 1` for every weight, all zero for the expected constants,
+//! then `-1` itself, which the verifier compares against the field's -1.
+use circuits::{
+    interface::CircuitTr,
+    layered_circuit::{LayeredCircuit, builder::CircuitBuilder},
+};
+use p3_field::{ExtensionField, Field};
+use poly::Fields;
+
+use crate::primitives::SumCheckProof;
+
+/// The checks output by the circuit, followed by the final claim
+pub type CircuitChecks<F, E> = (Vec<Fields<F, E>>, Fields<F, E>);
+
+/// Sumcheck verifier circuit for a fixed round count and round poly degree
+#[derive(Debug, Clone)]
+pub struct SumCheckVerifierCircuit {
+    /// The generated circuit
+    circuit: LayeredCircuit,
+    /// Number of sumcheck rounds
+    num_rounds: usize,
+    /// Degree of the round polys
+    degree: usize,
+    /// Number of circuit inputs
+    num_inputs: usize,
+}
+
+impl SumCheckVerifierCircuit {
+    /// Generates the verifier circuit for `num_rounds` rounds of degree `degree` round polys
+    pub fn new(num_rounds: usize, degree: usize) -> Self {
+        assert!(num_rounds > 0, "verifier circuit needs at least one round");
+        assert!(
+            degree > 0,
+            "round polys need at least the evaluations at 0 and 1"
+        );
+
+        let mut builder = CircuitBuilder::new();
+        let minus_one = builder.input();
+        let minus_points = builder.inputs(degree);
+        let weights = builder.inputs(degree + 1);
+        let mut claim = builder.input();
+        let round_polys = (0..num_rounds)
+            .map(|_| builder.inputs(degree + 1))
+            .collect::<Vec<_>>();
+        let challenges = builder.inputs(num_rounds);
+
+        let mut outputs = Vec::with_capacity(num_rounds + 2 * degree + 4);
+        for (round_poly, challenge) in round_polys.iter().zip(challenges) {
+            // claim - (p(0) + p(1))
+            let round_sum = builder.add(round_poly[0], round_poly[1]);
+            let negated = builder.mul(minus_one, round_sum);
+            outputs.push(builder.add(claim, negated));
+
+            // p(r) = Σ_j w_j · p(j) · Π_{m ≠ j} (r - m)
+            let differences = std::iter::once(challenge)
+                .chain(
+                    minus_points
+                        .iter()
+                        .map(|minus_point| builder.add(challenge, *minus_point)),
+                )
+                .collect::<Vec<_>>();
+            let terms = (0..=degree)
+                .map(|j| {
+                    let factors = [weights[j], round_poly[j]]
+                        .into_iter()
+                        .chain(
+                            differences
+                                .iter()
+                                .enumerate()
+                                .filter(|(m, _)| *m != j)
+                                .map(|(_, difference)| *difference),
+                        )
+                        .collect::<Vec<_>>();
+                    builder.product(&factors)
+                })
+                .collect::<Vec<_>>();
+            claim = builder.sum(&terms);
+        }
+
+        // -k for k in 0..=d
+        let points = std::iter::once(builder.zero())
+            .chain(minus_points.iter().copied())
+            .collect::<Vec<_>>();
+        outputs.push(builder.zero());
+        for m in 1..=degree {
+            // -m + (-1) · (-(m - 1) + (-1))
+            let previous = builder.add(points[m - 1], minus_one);
+            let negated = builder.mul(minus_one, previous);
+            outputs.push(builder.add(points[m], negated));
+        }
+        for j in 0..=degree {
+            // w_j · Π_{m ≠ j} ((-m) + (-1) · (-j)) + (-1)
+            let minus_j = builder.mul(minus_one, points[j]);
+            let factors = std::iter::once(weights[j])
+                .chain(
+                    (0..=degree)
+                        .filter(|m| *m != j)
+                        .map(|m| builder.add(points[m], minus_j)),
+                )
+                .collect::<Vec<_>>();
+            let product = builder.product(&factors);
+            outputs.push(builder.add(product, minus_one));
+        }
+        outputs.push(minus_one);
+        outputs.push(claim);
+
+        Self {
+            circuit: builder.build(&outputs),
+            num_rounds,
+            degree,
+            num_inputs: builder.num_inputs(),
+        }
+    }
+
+    /// The generated circuit
+    pub fn circuit(&self) -> &LayeredCircuit {
+        &self.circuit
+    }
+
+    /// Number of circuit inputs
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// Assembles the circuit inputs for a proof, using the challenges stored in the proof.
+    /// Errors when the proof does not have the circuit's shape.
+    pub fn inputs<F: Field, E: ExtensionField<F>>(
+        &self,
+        proof: &SumCheckProof<F, E>,
+    ) -> Result<Vec<Fields<F, E>>, anyhow::Error> {
+        anyhow::ensure!(
+            proof.round_polynomials.len() == self.num_rounds,
+            "proof has {} round polynomials, the circuit verifies {} rounds",
+            proof.round_polynomials.len(),
+            self.num_rounds
+        );
+        anyhow::ensure!(
+            proof.challenges.len() == self.num_rounds,
+            "proof has {} challenges, the circuit verifies {} rounds",
+            proof.challenges.len(),
+            self.num_rounds
+        );
+
+        let point = |m: usize| F::from_canonical_usize(m);
+        let mut inputs = Vec::with_capacity(self.num_inputs);
+        inputs.push(Fields::Base(F::zero()));
+        inputs.push(Fields::Base(-F::one()));
+        inputs.extend((1..=self.degree).map(|m| Fields::Base(-point(m))));
+        inputs.extend((0..=self.degree).map(|j| {
+            let denominator = (0..=self.degree)
+                .filter(|m| *m != j)
+                .fold(F::one(), |acc, m| acc * (point(j) - point(m)));
+            Fields::Base(denominator.inverse())
+        }));
+        inputs.push(proof.claimed_sum);
+        for round_poly in &proof.round_polynomials {
+            anyhow::ensure!(
+                round_poly.len() == self.degree + 1,
+                "round polynomial has {} evaluations, expected {}",
+                round_poly.len(),
+                self.degree + 1
+            );
+            inputs.extend(round_poly.evaluations());
+        }
+        inputs.extend_from_slice(&proof.challenges);
+        Ok(inputs)
+    }
+
+    /// Runs the circuit on a proof, returns the checks and the final claim
+    pub fn evaluate<F: Field, E: ExtensionField<F>>(
+        &self,
+        proof: &SumCheckProof<F, E>,
+    ) -> Result<CircuitChecks<F, E>, anyhow::Error> {
+        self.evaluate_inputs(&self.inputs(proof)?)
+    }
+
+    /// Runs the circuit on assembled inputs, returns the per-round checks followed by the
+    /// constant checks and the final claim. Every check is zero for an accepting proof.
+    pub fn evaluate_inputs<F: Field, E: ExtensionField<F>>(
+        &self,
+        inputs: &[Fields<F, E>],
+    ) -> Result<CircuitChecks<F, E>, anyhow::Error> {
+        anyhow::ensure!(
+            inputs.len() == self.num_inputs,
+            "circuit takes {} inputs, got {}",
+            self.num_inputs,
+            inputs.len()
+        );
+        let trace = self.circuit.excecute(inputs);
+        let mut outputs = trace.layers.last().expect("circuit has layers").clone();
+        let final_claim = outputs.pop().expect("circuit outputs the final claim");
+        let minus_one = outputs.pop().expect("circuit outputs -1");
+        outputs.push(minus_one + Fields::Base(F::one()));
+        Ok((outputs, final_claim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, Field, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{
+        Fields, MultilinearExtension, mle::MultilinearPoly, utils::product_poly, vpoly::VPoly,
+    };
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use transcript::Transcript;
+
+    use super::SumCheckVerifierCircuit;
+    use crate::{SumCheck, interface::SumCheckInterface};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn random_mle(rng: &mut StdRng, num_vars: usize) -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(
+            num_vars,
+            (0..1 << num_vars)
+                .map(|_| Fields::Base(F::from_canonical_u32(rng.gen_range(0..1 << 20))))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_circuit_agrees_with_verify_partial() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for (num_vars, degree) in [(1, 1), (3, 2), (4, 3)] {
+            let polynomial = product_poly(
+                (0..degree)
+                    .map(|_| random_mle(&mut rng, num_vars))
+                    .collect(),
+            );
+            let claimed_sum = polynomial.sum_over_hypercube();
+
+            let proof = SumCheck::prove_partial(
+                claimed_sum,
+                &mut polynomial.clone(),
+                &mut Transcript::init(),
+            )
+            .unwrap();
+            let (claim, challenges) = SumCheck::<F, E, VPoly<F, E>>::verify_partial(
+                &proof,
+                num_vars,
+                degree,
+                &mut Transcript::init(),
+            )
+            .unwrap();
+            assert_eq!(challenges, proof.challenges);
+
+            let circuit = SumCheckVerifierCircuit::new(num_vars, degree);
+            assert_eq!(circuit.inputs(&proof).unwrap().len(), circuit.num_inputs());
+            let (round_checks, final_claim) = circuit.evaluate(&proof).unwrap();
+            assert!(
+                round_checks
+                    .iter()
+                    .all(|check| check.to_extension_field().is_zero())
+            );
+            assert_eq!(final_claim.to_extension_field(), claim);
+        }
+    }
+
+    #[test]
+    fn test_circuit_flags_inconsistent_round() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let polynomial = product_poly(vec![random_mle(&mut rng, 3), random_mle(&mut rng, 3)]);
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let mut proof = SumCheck::prove_partial(
+            claimed_sum,
+            &mut polynomial.clone(),
+            &mut Transcript::init(),
+        )
+        .unwrap();
+        proof.round_polynomials[1].values_mut()[0] += Fields::Base(F::one());
+
+        let (round_checks, _) = SumCheckVerifierCircuit::new(3, 2).evaluate(&proof).unwrap();
+        assert!(round_checks[0].to_extension_field().is_zero());
+        assert!(!round_checks[1].to_extension_field().is_zero());
+    }
+
+    #[test]
+    fn test_circuit_flags_wrong_constants() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let polynomial = product_poly(vec![random_mle(&mut rng, 3), random_mle(&mut rng, 3)]);
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let proof = SumCheck::prove_partial(
+            claimed_sum,
+            &mut polynomial.clone(),
+            &mut Transcript::init(),
+        )
+        .unwrap();
+        let circuit = SumCheckVerifierCircuit::new(3, 2);
+        let inputs = circuit.inputs(&proof).unwrap();
+        let accepts = |inputs: &[Fields<F, E>]| {
+            let (checks, _) = circuit.evaluate_inputs(inputs).unwrap();
+            checks
+                .iter()
+                .all(|check| check.to_extension_field().is_zero())
+        };
+        assert!(accepts(&inputs));
+
+        // zero, -1, -1 and -2, then the three weights
+        for index in 0..7 {
+            let mut forged = inputs.clone();
+            forged[index] += Fields::Base(F::one());
+            assert!(!accepts(&forged), "constant input {index} is not pinned");
+        }
+
+        // zeroing every constant zeroes every product, but -1 is compared to the field's
+        let mut forged = inputs;
+        for input in forged.iter_mut().take(7) {
+            *input = Fields::Base(F::zero());
+        }
+        assert!(!accepts(&forged));
+    }
+
+    #[test]
+    fn test_circuit_rejects_misshapen_proofs() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let polynomial = product_poly(vec![random_mle(&mut rng, 3), random_mle(&mut rng, 3)]);
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let proof = SumCheck::prove_partial(
+            claimed_sum,
+            &mut polynomial.clone(),
+            &mut Transcript::init(),
+        )
+        .unwrap();
+        let circuit = SumCheckVerifierCircuit::new(3, 2);
+
+        let mut missing_round = proof.clone();
+        missing_round.round_polynomials.pop();
+        assert!(circuit.evaluate(&missing_round).is_err());
+
+        let mut missing_challenge = proof.clone();
+        missing_challenge.challenges.pop();
+        assert!(circuit.inputs(&missing_challenge).is_err());
+
+        let mut wrong_degree = proof.clone();
+        wrong_degree.round_polynomials[2]
+            .values_mut()
+            .push(Fields::Base(F::one()));
+        assert!(circuit.evaluate(&wrong_degree).is_err());
+
+        let inputs = circuit.inputs(&proof).unwrap();
+        assert!(circuit.evaluate_inputs(&inputs[1..]).is_err());
+    }
+}
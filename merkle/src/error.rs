@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+
+/// Reasons a Merkle tree can not be built, opened, or an opening is rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    /// Trees are built over a non-zero power of two number of leaves
+    InvalidLeafCount { num_leaves: usize },
+    /// An opening must contain at least one leaf
+    EmptyOpening,
+    /// Leaf index outside of the tree
+    IndexOutOfRange { index: usize, num_leaves: usize },
+    /// Different number of indices and opened leaves
+    LeafCountMismatch { indices: usize, leaves: usize },
+    /// The same index was opened to two different leaves
+    InconsistentLeaves { index: usize },
+    /// The proof ran out of siblings before reaching the root
+    MissingSiblings,
+    /// Siblings left over once the root was reached
+    UnusedSiblings { count: usize },
+    /// The recomputed root differs from the commitment
+    RootMismatch,
+}
+
+impl Display for MerkleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MerkleError::InvalidLeafCount { num_leaves } => {
+                write!(
+                    f,
+                    "number of leaves must be a power of two, got {num_leaves}"
+                )
+            }
+            MerkleError::EmptyOpening => write!(f, "opening has no leaves"),
+            MerkleError::IndexOutOfRange { index, num_leaves } => {
+                write!(f, "leaf index {index} out of range for {num_leaves} leaves")
+            }
+            MerkleError::LeafCountMismatch { indices, leaves } => {
+                write!(f, "{indices} indices opened with {leaves} leaves")
+            }
+            MerkleError::InconsistentLeaves { index } => {
+                write!(f, "leaf {index} opened to two different values")
+            }
+            MerkleError::MissingSiblings => write!(f, "proof is missing siblings"),
+            MerkleError::UnusedSiblings { count } => {
+                write!(f, "proof has {count} unused siblings")
+            }
+            MerkleError::RootMismatch => write!(f, "opening does not match the root"),
+        }
+    }
+}
+
+impl std::error::Error for MerkleError {}
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use fields::Fields;
+use p3_field::{ExtensionField, Field, PrimeField64};
+use p3_keccak::Keccak256Hash;
+use p3_symmetric::CryptographicHasher;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Hash function of a Merkle tree over leaves of `Fields<F, E>`
+pub trait MerkleHasher<F: Field>: Clone {
+    type Digest: Copy + Eq + Debug + Serialize + DeserializeOwned;
+
+    /// Hashes a leaf, leaves and inner nodes must be domain separated
+    fn hash_leaf<E: ExtensionField<F>>(&self, leaf: &[Fields<F, E>]) -> Self::Digest;
+
+    /// Hashes two children into their parent
+    fn hash_node(&self, left: &Self::Digest, right: &Self::Digest) -> Self::Digest;
+}
+
+/// Keccak digest
+pub type KeccakDigest = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Keccak256 over the canonical little endian encoding of the elements.
+/// Every element is tagged as base or extension, so the two never collide.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeccakHasher;
+
+impl<F: PrimeField64> MerkleHasher<F> for KeccakHasher {
+    type Digest = KeccakDigest;
+
+    fn hash_leaf<E: ExtensionField<F>>(&self, leaf: &[Fields<F, E>]) -> KeccakDigest {
+        let bytes = leaf.iter().flat_map(|value| {
+            let (tag, coeffs) = match value {
+                Fields::Base(value) => (0u8, vec![*value]),
+                Fields::Extension(value) => (1u8, value.as_base_slice().to_vec()),
+            };
+            std::iter::once(tag).chain(
+                coeffs
+                    .into_iter()
+                    .flat_map(|coeff| coeff.as_canonical_u64().to_le_bytes()),
+            )
+        });
+        Keccak256Hash.hash_iter(std::iter::once(LEAF_PREFIX).chain(bytes))
+    }
+
+    fn hash_node(&self, left: &KeccakDigest, right: &KeccakDigest) -> KeccakDigest {
+        Keccak256Hash.hash_iter(
+            std::iter::once(NODE_PREFIX)
+                .chain(left.iter().copied())
+                .chain(right.iter().copied()),
+        )
+    }
+}
@@ -0,0 +1,12 @@
+//! Merkle trees over rows of `Fields`, with pluggable hashing and batch openings.
+//! A batch opening of several leaves shares the siblings common to their paths, and the
+//! verifier reports why an opening is rejected with a typed `MerkleError`.
+pub mod error;
+pub mod hasher;
+#[cfg(feature = "poseidon2")]
+pub mod poseidon2;
+pub mod tree;
+
+pub use error::MerkleError;
+pub use hasher::{KeccakDigest, KeccakHasher, MerkleHasher};
+pub use tree::{MerkleProof, MerkleTree};
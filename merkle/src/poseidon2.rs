@@ -0,0 +1,65 @@
+//! Poseidon2 hashing over Mersenne31, for trees that are cheap to open inside a circuit.
+//! Leaves are absorbed by a padding-free sponge and nodes are compressed with the
+//! truncated permutation, both over the transcript permutation.
+use fields::Fields;
+use p3_field::{AbstractField, ExtensionField};
+use p3_mersenne_31::Mersenne31;
+use p3_symmetric::{
+    CryptographicHasher, PaddingFreeSponge, PseudoCompressionFunction, TruncatedPermutation,
+};
+use transcript::poseidon2::{
+    POSEIDON2_RATE, POSEIDON2_WIDTH, Poseidon2Mersenne31, poseidon2_permutation,
+};
+
+use crate::MerkleHasher;
+
+/// Number of elements of a digest
+pub const POSEIDON2_DIGEST_ELEMS: usize = 8;
+
+/// Poseidon2 digest
+pub type Poseidon2Digest = [Mersenne31; POSEIDON2_DIGEST_ELEMS];
+
+#[derive(Clone)]
+pub struct Poseidon2Hasher {
+    sponge: PaddingFreeSponge<
+        Poseidon2Mersenne31,
+        POSEIDON2_WIDTH,
+        POSEIDON2_RATE,
+        POSEIDON2_DIGEST_ELEMS,
+    >,
+    compression:
+        TruncatedPermutation<Poseidon2Mersenne31, 2, POSEIDON2_DIGEST_ELEMS, POSEIDON2_WIDTH>,
+}
+
+impl Default for Poseidon2Hasher {
+    fn default() -> Self {
+        Self {
+            sponge: PaddingFreeSponge::new(poseidon2_permutation().clone()),
+            compression: TruncatedPermutation::new(poseidon2_permutation().clone()),
+        }
+    }
+}
+
+impl MerkleHasher<Mersenne31> for Poseidon2Hasher {
+    type Digest = Poseidon2Digest;
+
+    fn hash_leaf<E: ExtensionField<Mersenne31>>(
+        &self,
+        leaf: &[Fields<Mersenne31, E>],
+    ) -> Poseidon2Digest {
+        // the leaf length and the element tags keep leaves of different shapes apart
+        let elements = leaf.iter().flat_map(|value| match value {
+            Fields::Base(value) => vec![Mersenne31::zero(), *value],
+            Fields::Extension(value) => std::iter::once(Mersenne31::one())
+                .chain(value.as_base_slice().iter().copied())
+                .collect(),
+        });
+        self.sponge.hash_iter(
+            std::iter::once(Mersenne31::from_canonical_usize(leaf.len())).chain(elements),
+        )
+    }
+
+    fn hash_node(&self, left: &Poseidon2Digest, right: &Poseidon2Digest) -> Poseidon2Digest {
+        self.compression.compress([*left, *right])
+    }
+}
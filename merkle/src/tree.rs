@@ -0,0 +1,384 @@
+use std::marker::PhantomData;
+
+use fields::Fields;
+use p3_field::{ExtensionField, Field};
+use serde::{Deserialize, Serialize};
+
+use crate::{MerkleError, MerkleHasher};
+
+/// Siblings needed to recompute the root from a set of opened leaves.
+/// Siblings are listed layer by layer from the leaves up, left to right within a layer,
+/// and nodes that can be recomputed from the opened leaves are omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof<D> {
+    pub siblings: Vec<D>,
+}
+
+/// Merkle tree over a power of two number of leaves
+#[derive(Debug, Clone)]
+pub struct MerkleTree<F: Field, H: MerkleHasher<F>> {
+    hasher: H,
+    /// Node layers, from the leaf digests up to the root
+    layers: Vec<Vec<H::Digest>>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field, H: MerkleHasher<F>> MerkleTree<F, H> {
+    /// Builds the tree over rows of field elements
+    pub fn new<E: ExtensionField<F>>(
+        hasher: H,
+        leaves: &[Vec<Fields<F, E>>],
+    ) -> Result<Self, MerkleError> {
+        let digests = leaves.iter().map(|leaf| hasher.hash_leaf(leaf)).collect();
+        Self::from_leaf_digests(hasher, digests)
+    }
+
+    /// Builds the tree over already hashed leaves
+    pub fn from_leaf_digests(hasher: H, digests: Vec<H::Digest>) -> Result<Self, MerkleError> {
+        check_leaf_count(digests.len())?;
+        let mut layers = vec![digests];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hasher.hash_node(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        Ok(Self {
+            hasher,
+            layers,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The root of the tree
+    pub fn root(&self) -> H::Digest {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Authentication path of the leaf at `index`
+    pub fn open(&self, index: usize) -> Result<MerkleProof<H::Digest>, MerkleError> {
+        self.open_batch(&[index])
+    }
+
+    /// Opens several leaves at once, siblings shared by their paths are sent once.
+    /// Indices may be given in any order and repeated.
+    pub fn open_batch(&self, indices: &[usize]) -> Result<MerkleProof<H::Digest>, MerkleError> {
+        let mut known = sorted_indices(indices, self.num_leaves())?;
+        let mut siblings = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let mut position = 0;
+            while position < known.len() {
+                let index = known[position];
+                if index % 2 == 0 && known.get(position + 1) == Some(&(index + 1)) {
+                    position += 2;
+                } else {
+                    siblings.push(layer[index ^ 1]);
+                    position += 1;
+                }
+            }
+            known = parents(&known);
+        }
+        Ok(MerkleProof { siblings })
+    }
+
+    /// Checks the opening of `leaf` at `index` against `root`
+    pub fn verify<E: ExtensionField<F>>(
+        hasher: &H,
+        root: &H::Digest,
+        num_leaves: usize,
+        index: usize,
+        leaf: &[Fields<F, E>],
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), MerkleError> {
+        Self::verify_digests(
+            hasher,
+            root,
+            num_leaves,
+            &[(index, hasher.hash_leaf(leaf))],
+            proof,
+        )
+    }
+
+    /// Checks a batch opening of `leaves` at `indices` against `root`
+    pub fn verify_batch<E: ExtensionField<F>>(
+        hasher: &H,
+        root: &H::Digest,
+        num_leaves: usize,
+        indices: &[usize],
+        leaves: &[Vec<Fields<F, E>>],
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), MerkleError> {
+        if indices.len() != leaves.len() {
+            return Err(MerkleError::LeafCountMismatch {
+                indices: indices.len(),
+                leaves: leaves.len(),
+            });
+        }
+        let openings = indices
+            .iter()
+            .zip(leaves)
+            .map(|(index, leaf)| (*index, hasher.hash_leaf(leaf)))
+            .collect::<Vec<_>>();
+        Self::verify_digests(hasher, root, num_leaves, &openings, proof)
+    }
+
+    /// Checks a batch opening of `(index, leaf digest)` pairs against `root`
+    pub fn verify_digests(
+        hasher: &H,
+        root: &H::Digest,
+        num_leaves: usize,
+        openings: &[(usize, H::Digest)],
+        proof: &MerkleProof<H::Digest>,
+    ) -> Result<(), MerkleError> {
+        check_leaf_count(num_leaves)?;
+        if openings.is_empty() {
+            return Err(MerkleError::EmptyOpening);
+        }
+        let mut nodes = openings.to_vec();
+        nodes.sort_by_key(|(index, _)| *index);
+        nodes.dedup_by(|next, prev| next.0 == prev.0 && next.1 == prev.1);
+        for pair in nodes.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(MerkleError::InconsistentLeaves { index: pair[0].0 });
+            }
+        }
+        if let Some((index, _)) = nodes.last().filter(|(index, _)| *index >= num_leaves) {
+            return Err(MerkleError::IndexOutOfRange {
+                index: *index,
+                num_leaves,
+            });
+        }
+
+        let mut siblings = proof.siblings.iter();
+        for _ in 0..num_leaves.ilog2() {
+            let mut next = Vec::with_capacity(nodes.len());
+            let mut position = 0;
+            while position < nodes.len() {
+                let (index, node) = nodes[position];
+                let parent = match nodes.get(position + 1) {
+                    Some((right_index, right)) if index % 2 == 0 && *right_index == index + 1 => {
+                        position += 2;
+                        hasher.hash_node(&node, right)
+                    }
+                    _ => {
+                        position += 1;
+                        let sibling = siblings.next().ok_or(MerkleError::MissingSiblings)?;
+                        if index % 2 == 0 {
+                            hasher.hash_node(&node, sibling)
+                        } else {
+                            hasher.hash_node(sibling, &node)
+                        }
+                    }
+                };
+                next.push((index / 2, parent));
+            }
+            nodes = next;
+        }
+
+        let unused = siblings.count();
+        if unused > 0 {
+            return Err(MerkleError::UnusedSiblings { count: unused });
+        }
+        if nodes[0].1 != *root {
+            return Err(MerkleError::RootMismatch);
+        }
+        Ok(())
+    }
+}
+
+fn check_leaf_count(num_leaves: usize) -> Result<(), MerkleError> {
+    if !num_leaves.is_power_of_two() {
+        return Err(MerkleError::InvalidLeafCount { num_leaves });
+    }
+    Ok(())
+}
+
+/// Sorted, deduplicated indices, all in range
+fn sorted_indices(indices: &[usize], num_leaves: usize) -> Result<Vec<usize>, MerkleError> {
+    if indices.is_empty() {
+        return Err(MerkleError::EmptyOpening);
+    }
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+    match indices.last() {
+        Some(index) if *index >= num_leaves => Err(MerkleError::IndexOutOfRange {
+            index: *index,
+            num_leaves,
+        }),
+        _ => Ok(indices),
+    }
+}
+
+/// Parents of sorted node indices, deduplicated
+fn parents(indices: &[usize]) -> Vec<usize> {
+    let mut parents = indices.iter().map(|index| index / 2).collect::<Vec<_>>();
+    parents.dedup();
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use fields::Fields;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use crate::{KeccakHasher, MerkleError, MerkleProof, MerkleTree};
+
+    type E = BinomialExtensionField<F, 3>;
+    type Tree = MerkleTree<F, KeccakHasher>;
+
+    fn leaves(num_leaves: u32) -> Vec<Vec<Fields<F, E>>> {
+        (0..num_leaves)
+            .map(|i| {
+                vec![
+                    Fields::Base(F::from_canonical_u32(i)),
+                    Fields::Extension(E::from_canonical_u32(i + 100)),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_openings() {
+        let leaves = leaves(8);
+        let tree = Tree::new(KeccakHasher, &leaves).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.open(index).unwrap();
+            assert_eq!(proof.siblings.len(), 3);
+            assert_eq!(
+                Tree::verify(&KeccakHasher, &tree.root(), 8, index, leaf, &proof),
+                Ok(())
+            );
+            assert_eq!(
+                Tree::verify(&KeccakHasher, &tree.root(), 8, index ^ 1, leaf, &proof),
+                Err(MerkleError::RootMismatch)
+            );
+        }
+        assert_eq!(
+            Tree::verify(
+                &KeccakHasher,
+                &tree.root(),
+                8,
+                8,
+                &leaves[0],
+                &tree.open(0).unwrap()
+            ),
+            Err(MerkleError::IndexOutOfRange {
+                index: 8,
+                num_leaves: 8
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_openings_share_siblings() {
+        let leaves = leaves(16);
+        let tree = Tree::new(KeccakHasher, &leaves).unwrap();
+        let indices = [5, 4, 13, 0, 5];
+        let opened = indices
+            .iter()
+            .map(|index| leaves[*index].clone())
+            .collect::<Vec<_>>();
+
+        let proof = tree.open_batch(&indices).unwrap();
+        // three separate paths would need 12 siblings
+        assert_eq!(proof.siblings.len(), 6);
+        assert_eq!(
+            Tree::verify_batch(&KeccakHasher, &tree.root(), 16, &indices, &opened, &proof),
+            Ok(())
+        );
+
+        let mut tampered = opened.clone();
+        tampered[2][0] = Fields::Base(F::zero());
+        assert_eq!(
+            Tree::verify_batch(&KeccakHasher, &tree.root(), 16, &indices, &tampered, &proof),
+            Err(MerkleError::RootMismatch)
+        );
+
+        let mut tampered = opened.clone();
+        tampered[4] = leaves[6].clone();
+        assert_eq!(
+            Tree::verify_batch(&KeccakHasher, &tree.root(), 16, &indices, &tampered, &proof),
+            Err(MerkleError::InconsistentLeaves { index: 5 })
+        );
+
+        let short = MerkleProof {
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        assert_eq!(
+            Tree::verify_batch(&KeccakHasher, &tree.root(), 16, &indices, &opened, &short),
+            Err(MerkleError::MissingSiblings)
+        );
+
+        let mut long = proof.clone();
+        long.siblings.push(tree.root());
+        assert_eq!(
+            Tree::verify_batch(&KeccakHasher, &tree.root(), 16, &indices, &opened, &long),
+            Err(MerkleError::UnusedSiblings { count: 1 })
+        );
+
+        assert_eq!(
+            Tree::verify_batch(
+                &KeccakHasher,
+                &tree.root(),
+                16,
+                &indices[1..],
+                &opened,
+                &proof
+            ),
+            Err(MerkleError::LeafCountMismatch {
+                indices: 4,
+                leaves: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_proof_serialization() {
+        let tree = Tree::new(KeccakHasher, &leaves(8)).unwrap();
+        let proof = tree.open_batch(&[1, 6]).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            serde_json::from_str::<MerkleProof<_>>(&json).unwrap(),
+            proof
+        );
+
+        assert_eq!(
+            Tree::new(KeccakHasher, &leaves(6)).unwrap_err(),
+            MerkleError::InvalidLeafCount { num_leaves: 6 }
+        );
+    }
+
+    #[cfg(feature = "poseidon2")]
+    #[test]
+    fn test_poseidon2_openings() {
+        use crate::poseidon2::Poseidon2Hasher;
+
+        let leaves = leaves(8);
+        let hasher = Poseidon2Hasher::default();
+        let tree = MerkleTree::new(hasher.clone(), &leaves).unwrap();
+        let proof = tree.open_batch(&[2, 3, 7]).unwrap();
+        let opened = [2, 3, 7].map(|index| leaves[index].clone());
+        assert_eq!(
+            MerkleTree::verify_batch(&hasher, &tree.root(), 8, &[2, 3, 7], &opened, &proof),
+            Ok(())
+        );
+        assert_eq!(
+            MerkleTree::verify_batch(&hasher, &tree.root(), 8, &[2, 3, 6], &opened, &proof),
+            Err(MerkleError::RootMismatch)
+        );
+    }
+}
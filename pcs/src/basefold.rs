@@ -0,0 +1,610 @@
+//! Basefold multilinear polynomial commitment scheme (Zeilberger, Chen and Fisch).
+//! The evaluations of the polynomial are encoded with the random foldable code and the
+//! codeword is committed with a Merkle tree. An opening at z runs a sumcheck over
+//! f(x) · eq(z, x) and folds the codeword with every sumcheck challenge, committing each
+//! folded codeword. The final folded value is f(r), which closes the sumcheck, and
+//! random queries check that every fold was computed honestly.
+use std::marker::PhantomData;
+
+use anyhow::{Context, ensure};
+use p3_field::{ExtensionField, Field, PrimeField64};
+use poly::{
+    Fields, MultilinearExtension,
+    barycentric::BarycentricDomain,
+    mle::{MleEvaluations, MultilinearPoly, VariableOrder},
+    univariate::UnivariatePoly,
+    utils::generate_eq,
+};
+use transcript::{Transcript, TranscriptChallenger, absorb::Bytes};
+
+use crate::{code::FoldableCode, interface::PolynomialCommitmentScheme};
+use merkle::{KeccakDigest, KeccakHasher, MerkleProof, MerkleTree};
+
+/// Seed of the code weights
+const BASEFOLD_CODE_SEED: u64 = 0xba5e_f01d;
+/// Transcript label of the commitment
+const COMMITMENT_LABEL: &[u8] = b"basefold_commitment";
+/// Transcript label of the opening point
+const POINT_LABEL: &[u8] = b"basefold_point";
+/// Transcript label of the claimed evaluation
+const EVALUATION_LABEL: &[u8] = b"basefold_evaluation";
+/// Transcript label of a sumcheck round polynomial
+const ROUND_POLY_LABEL: &[u8] = b"basefold_round_poly";
+/// Transcript label of a sumcheck round challenge
+const ROUND_CHALLENGE_LABEL: &[u8] = b"basefold_round_challenge";
+/// Transcript label of the root of a folded codeword
+const FOLD_ROOT_LABEL: &[u8] = b"basefold_fold_root";
+/// Transcript label of the fully folded value
+const FINAL_VALUE_LABEL: &[u8] = b"basefold_final_value";
+/// Transcript label preceding the query indices
+const QUERIES_LABEL: &[u8] = b"basefold_queries";
+
+/// Public parameters of Basefold
+#[derive(Debug, Clone)]
+pub struct BasefoldParams<F: Field> {
+    /// Number of variables of the committed polynomials
+    num_vars: usize,
+    /// Number of codeword queries per opening
+    num_queries: usize,
+    /// Code shared by the prover and the verifier
+    code: FoldableCode<F>,
+}
+
+impl<F: Field> BasefoldParams<F> {
+    /// Parameters for `num_vars`-variate polynomials, codewords are 2^log_rate times longer
+    /// than the evaluations
+    pub fn new(num_vars: usize, log_rate: usize, num_queries: usize) -> Self {
+        assert!(num_vars > 0, "basefold commits to at least one variable");
+        assert!(num_queries > 0, "basefold needs at least one query");
+        Self {
+            num_vars,
+            num_queries,
+            code: FoldableCode::new(num_vars, log_rate, BASEFOLD_CODE_SEED),
+        }
+    }
+
+    /// Length of the committed codeword
+    pub fn codeword_len(&self) -> usize {
+        1 << (self.num_vars + self.code.log_rate())
+    }
+}
+
+/// Commitment to a multilinear polynomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasefoldCommitment {
+    /// Merkle root of the codeword
+    pub root: KeccakDigest,
+    /// Number of variables of the polynomial
+    pub num_vars: usize,
+}
+
+/// Data the prover keeps to open a commitment
+#[derive(Debug, Clone)]
+pub struct BasefoldProverData<F: PrimeField64, E: ExtensionField<F>> {
+    polynomial: MultilinearPoly<F, E>,
+    /// Codeword in the storage field of the polynomial
+    codeword: MleEvaluations<F, E>,
+    tree: MerkleTree<F, KeccakHasher>,
+}
+
+/// Openings of one folding step, for every query the codeword pair (c[j], c[j + n / 2])
+/// it folds, with a single batch Merkle proof. Pairs of the first step are base elements
+/// when a base field polynomial is committed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoldOpening<F: Field, E: ExtensionField<F>> {
+    pub values: Vec<[Fields<F, E>; 2]>,
+    pub proof: MerkleProof<KeccakDigest>,
+}
+
+/// Basefold evaluation proof
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasefoldProof<F: Field, E: ExtensionField<F>> {
+    /// Sumcheck round polynomials, of degree 2
+    pub round_polynomials: Vec<UnivariatePoly<F, E>>,
+    /// Roots of the folded codewords, the last fold is sent as `final_value`
+    pub roots: Vec<KeccakDigest>,
+    /// The polynomial evaluated at the sumcheck challenges
+    pub final_value: E,
+    /// For every folding step, the openings of all queries
+    pub queries: Vec<FoldOpening<F, E>>,
+}
+
+pub struct Basefold<F: Field, E: ExtensionField<F>> {
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F: PrimeField64, E: ExtensionField<F>> PolynomialCommitmentScheme<F, E> for Basefold<F, E> {
+    type Params = BasefoldParams<F>;
+    type Commitment = BasefoldCommitment;
+    type ProverData = BasefoldProverData<F, E>;
+    type Proof = BasefoldProof<F, E>;
+
+    fn commit(
+        params: &Self::Params,
+        polynomial: &MultilinearPoly<F, E>,
+    ) -> Result<(Self::Commitment, Self::ProverData), anyhow::Error> {
+        ensure!(
+            polynomial.num_vars() == params.num_vars,
+            "expected a polynomial over {} variables, got {}",
+            params.num_vars,
+            polynomial.num_vars()
+        );
+        ensure!(
+            polynomial.order() == VariableOrder::MsbFirst,
+            "only MSB-first polynomials can be committed"
+        );
+        // base field polynomials are encoded and hashed in the base field
+        let codeword = match polynomial.evaluations() {
+            MleEvaluations::Base(message) => MleEvaluations::Base(params.code.encode(message)),
+            MleEvaluations::Extension(message) => {
+                MleEvaluations::Extension(params.code.encode(message))
+            }
+        };
+        let tree = commit_codeword(&codeword)?;
+
+        Ok((
+            BasefoldCommitment {
+                root: tree.root(),
+                num_vars: params.num_vars,
+            },
+            BasefoldProverData {
+                polynomial: polynomial.clone(),
+                codeword,
+                tree,
+            },
+        ))
+    }
+
+    fn open<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        prover_data: &Self::ProverData,
+        point: &[Fields<F, E>],
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<(E, Self::Proof), anyhow::Error> {
+        let num_vars = params.num_vars;
+        ensure!(
+            point.len() == num_vars,
+            "expected an opening point of {num_vars} coordinates, got {}",
+            point.len()
+        );
+        let evaluation = prover_data.polynomial.evaluate(point).to_extension_field();
+        let commitment = BasefoldCommitment {
+            root: prover_data.tree.root(),
+            num_vars,
+        };
+        bind_statement(transcript, &commitment, point, evaluation);
+
+        let mut polynomial = prover_data.polynomial.clone();
+        let mut eq = MultilinearPoly::new_from_vec(num_vars, generate_eq(point));
+        let mut codewords = vec![prover_data.codeword.clone()];
+        let mut trees = vec![prover_data.tree.clone()];
+        let mut round_polynomials = Vec::with_capacity(num_vars);
+        let mut roots = Vec::with_capacity(num_vars - 1);
+
+        for round in 0..num_vars {
+            let round_poly = round_message(&polynomial, &eq);
+            transcript.absorb(ROUND_POLY_LABEL, &round_poly);
+            let challenge = transcript.challenge(ROUND_CHALLENGE_LABEL);
+            round_polynomials.push(round_poly);
+
+            polynomial = polynomial.partial_evaluate(&[Fields::Extension(challenge)]);
+            eq = eq.partial_evaluate(&[Fields::Extension(challenge)]);
+            let folded = match codewords.last().unwrap() {
+                // the first fold lifts a base codeword to the extension field
+                MleEvaluations::Base(_) => {
+                    params.code.fold(&codewords[0].to_extension(), challenge)
+                }
+                MleEvaluations::Extension(codeword) => params.code.fold(codeword, challenge),
+            };
+            let folded = MleEvaluations::Extension(folded);
+            if round + 1 < num_vars {
+                let tree = commit_codeword(&folded)?;
+                transcript.absorb(FOLD_ROOT_LABEL, &Bytes(&tree.root()));
+                roots.push(tree.root());
+                trees.push(tree);
+            }
+            codewords.push(folded);
+        }
+
+        let final_value = polynomial.get(0).to_extension_field();
+        transcript.absorb(FINAL_VALUE_LABEL, &Fields::<F, E>::Extension(final_value));
+
+        let query_positions = sample_queries(params, transcript);
+        let queries = trees
+            .iter()
+            .zip(&codewords)
+            .map(|(tree, codeword)| {
+                let half = codeword.len() / 2;
+                let indices = query_positions
+                    .iter()
+                    .map(|query| query % half)
+                    .collect::<Vec<_>>();
+                Ok(FoldOpening {
+                    values: indices
+                        .iter()
+                        .map(|index| [codeword.get(*index), codeword.get(index + half)])
+                        .collect(),
+                    proof: tree.open_batch(&indices)?,
+                })
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+
+        Ok((
+            evaluation,
+            BasefoldProof {
+                round_polynomials,
+                roots,
+                final_value,
+                queries,
+            },
+        ))
+    }
+
+    fn verify<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &[Fields<F, E>],
+        evaluation: E,
+        proof: &Self::Proof,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<bool, anyhow::Error> {
+        let num_vars = params.num_vars;
+        ensure!(
+            commitment.num_vars == num_vars && point.len() == num_vars,
+            "commitment and point must have {num_vars} variables"
+        );
+        ensure!(
+            proof.round_polynomials.len() == num_vars && proof.roots.len() == num_vars - 1,
+            "proof must have {num_vars} rounds"
+        );
+        ensure!(
+            proof.queries.len() == num_vars
+                && proof
+                    .queries
+                    .iter()
+                    .all(|opening| opening.values.len() == params.num_queries),
+            "proof must answer {} queries at every folding step",
+            params.num_queries
+        );
+        bind_statement(transcript, commitment, point, evaluation);
+
+        // sumcheck over f(x) · eq(z, x), interleaved with the fold commitments
+        let mut claim = evaluation;
+        let mut challenges = Vec::with_capacity(num_vars);
+        // every round poly is quadratic, the weights over {0, 1, 2} are built once
+        let domain = BarycentricDomain::integers(3);
+        for (round, round_poly) in proof.round_polynomials.iter().enumerate() {
+            ensure!(
+                round_poly.len() == 3,
+                "round polynomial for round {round} must have degree 2"
+            );
+            ensure!(
+                (round_poly.evaluate_on(&domain, &Fields::Base(F::zero()))
+                    + round_poly.evaluate_on(&domain, &Fields::Base(F::one())))
+                .to_extension_field()
+                    == claim,
+                "sumcheck round {round} check failed"
+            );
+            transcript.absorb(ROUND_POLY_LABEL, round_poly);
+            let challenge = transcript.challenge(ROUND_CHALLENGE_LABEL);
+            claim = round_poly
+                .evaluate_on(&domain, &Fields::Extension(challenge))
+                .to_extension_field();
+            challenges.push(challenge);
+
+            if let Some(root) = proof.roots.get(round) {
+                transcript.absorb(FOLD_ROOT_LABEL, &Bytes(root));
+            }
+        }
+        transcript.absorb(
+            FINAL_VALUE_LABEL,
+            &Fields::<F, E>::Extension(proof.final_value),
+        );
+        ensure!(
+            claim == proof.final_value * eq_eval(point, &challenges),
+            "final sumcheck claim does not match the folded value"
+        );
+
+        // every query must fold consistently down to the final value
+        let roots = std::iter::once(&commitment.root)
+            .chain(&proof.roots)
+            .collect::<Vec<_>>();
+        let query_positions = sample_queries(params, transcript);
+        let mut folded: Vec<Option<E>> = vec![None; params.num_queries];
+        let mut codeword_len = params.codeword_len();
+        for (step, opening) in proof.queries.iter().enumerate() {
+            let half = codeword_len / 2;
+            let indices = query_positions
+                .iter()
+                .map(|query| query % half)
+                .collect::<Vec<_>>();
+            let leaves = opening
+                .values
+                .iter()
+                .map(|pair| pair.to_vec())
+                .collect::<Vec<_>>();
+            MerkleTree::verify_batch(
+                &KeccakHasher,
+                roots[step],
+                half,
+                &indices,
+                &leaves,
+                &opening.proof,
+            )
+            .with_context(|| format!("openings at step {step} are not in the commitment"))?;
+
+            for (query_index, (query, values)) in
+                query_positions.iter().zip(&opening.values).enumerate()
+            {
+                if let Some(folded) = folded[query_index] {
+                    let position = usize::from(query % codeword_len >= half);
+                    ensure!(
+                        values[position].to_extension_field() == folded,
+                        "query {query_index} fold at step {step} is inconsistent"
+                    );
+                }
+                folded[query_index] = Some(params.code.fold_pair(
+                    codeword_len,
+                    query % half,
+                    values.map(|value| value.to_extension_field()),
+                    challenges[step],
+                ));
+            }
+            codeword_len = half;
+        }
+        for (query_index, folded) in folded.into_iter().enumerate() {
+            ensure!(
+                folded == Some(proof.final_value),
+                "query {query_index} does not fold to the final value"
+            );
+        }
+
+        Ok(true)
+    }
+}
+
+/// Commits to a codeword, leaf j holds the pair folded together (c[j], c[j + n / 2])
+fn commit_codeword<F: PrimeField64, E: ExtensionField<F>>(
+    codeword: &MleEvaluations<F, E>,
+) -> Result<MerkleTree<F, KeccakHasher>, anyhow::Error> {
+    let half = codeword.len() / 2;
+    let leaves = (0..half)
+        .map(|index| vec![codeword.get(index), codeword.get(index + half)])
+        .collect::<Vec<_>>();
+    Ok(MerkleTree::new(KeccakHasher, &leaves)?)
+}
+
+/// Absorbs the commitment, the point and the claimed evaluation
+fn bind_statement<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    transcript: &mut Transcript<F, E, C>,
+    commitment: &BasefoldCommitment,
+    point: &[Fields<F, E>],
+    evaluation: E,
+) {
+    transcript.absorb(COMMITMENT_LABEL, &Bytes(&commitment.root));
+    transcript.absorb(COMMITMENT_LABEL, &commitment.num_vars);
+    transcript.absorb(POINT_LABEL, point);
+    transcript.absorb(EVALUATION_LABEL, &Fields::<F, E>::Extension(evaluation));
+}
+
+/// Samples the query positions in the first half of the committed codeword
+fn sample_queries<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    params: &BasefoldParams<F>,
+    transcript: &mut Transcript<F, E, C>,
+) -> Vec<usize> {
+    transcript.absorb(QUERIES_LABEL, &params.num_queries);
+    (0..params.num_queries)
+        .map(|_| transcript.sample_index(params.codeword_len() / 2))
+        .collect()
+}
+
+/// Evaluations at 0, 1 and 2 of the round polynomial of Σ f(x) · eq(x), fixing the
+/// most significant variable
+fn round_message<F: Field, E: ExtensionField<F>>(
+    polynomial: &MultilinearPoly<F, E>,
+    eq: &MultilinearPoly<F, E>,
+) -> UnivariatePoly<F, E> {
+    let half = polynomial.evaluations().len() / 2;
+    let mut evals = [E::zero(); 3];
+    for index in 0..half {
+        let f = [index, index + half].map(|i| polynomial.get(i).to_extension_field());
+        let e = [index, index + half].map(|i| eq.get(i).to_extension_field());
+        evals[0] += f[0] * e[0];
+        evals[1] += f[1] * e[1];
+        evals[2] += (f[1].double() - f[0]) * (e[1].double() - e[0]);
+    }
+    UnivariatePoly::from_evaluations(evals.into_iter().map(Fields::Extension).collect())
+}
+
+/// Evaluates eq(z, r) = Π (z_i r_i + (1 - z_i)(1 - r_i))
+fn eq_eval<F: Field, E: ExtensionField<F>>(point: &[Fields<F, E>], challenges: &[E]) -> E {
+    point.iter().zip(challenges).fold(E::one(), |acc, (z, r)| {
+        let z = z.to_extension_field();
+        acc * (z * *r + (E::one() - z) * (E::one() - *r))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks;
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use sum_check::{SumCheck, interface::SumCheckInterface};
+    use transcript::{KeccakChallenger64, Transcript};
+
+    use super::{Basefold, BasefoldParams};
+    use crate::interface::PolynomialCommitmentScheme;
+
+    type E = BinomialExtensionField<F, 3>;
+    type Pcs = Basefold<F, E>;
+
+    const NUM_VARS: usize = 5;
+
+    fn random_mle(rng: &mut StdRng) -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(
+            NUM_VARS,
+            (0..1 << NUM_VARS)
+                .map(|_| Fields::Base(F::from_canonical_u32(rng.gen_range(0..1 << 30))))
+                .collect(),
+        )
+    }
+
+    fn random_point(rng: &mut StdRng) -> Vec<Fields<F, E>> {
+        (0..NUM_VARS)
+            .map(|_| Fields::Extension(E::from_canonical_u32(rng.gen_range(0..1 << 30))))
+            .collect()
+    }
+
+    #[test]
+    fn test_basefold_open_and_verify() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let params = BasefoldParams::new(NUM_VARS, 2, 16);
+        let polynomial = random_mle(&mut rng);
+        let point = random_point(&mut rng);
+
+        let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) =
+            Pcs::open(&params, &prover_data, &point, &mut Transcript::init()).unwrap();
+        assert_eq!(evaluation, polynomial.evaluate(&point).to_extension_field());
+        // the base field codeword is opened in the base field, folds in the extension
+        assert!(
+            proof.queries[0]
+                .values
+                .iter()
+                .flatten()
+                .all(Fields::is_base_field)
+        );
+        assert!(
+            !proof.queries[1]
+                .values
+                .iter()
+                .flatten()
+                .any(Fields::is_base_field)
+        );
+
+        let verify = Pcs::verify(
+            &params,
+            &commitment,
+            &point,
+            evaluation,
+            &proof,
+            &mut Transcript::init(),
+        );
+        assert!(verify.unwrap());
+    }
+
+    #[test]
+    fn test_basefold_rejects_tampering() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let params = BasefoldParams::new(NUM_VARS, 2, 16);
+        let polynomial = random_mle(&mut rng);
+        let point = random_point(&mut rng);
+        let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) =
+            Pcs::open(&params, &prover_data, &point, &mut Transcript::init()).unwrap();
+
+        let verify = |commitment, evaluation, proof| {
+            Pcs::verify(
+                &params,
+                commitment,
+                &point,
+                evaluation,
+                proof,
+                &mut Transcript::init(),
+            )
+        };
+
+        assert!(verify(&commitment, evaluation + E::one(), &proof).is_err());
+
+        let mut tampered = proof.clone();
+        let value = tampered.queries[2].values[3][0].to_extension_field();
+        tampered.queries[2].values[3][0] = Fields::Extension(value + E::one());
+        assert!(verify(&commitment, evaluation, &tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.final_value += E::one();
+        assert!(verify(&commitment, evaluation, &tampered).is_err());
+
+        let (other_commitment, _) = Pcs::commit(&params, &random_mle(&mut rng)).unwrap();
+        assert!(verify(&other_commitment, evaluation, &proof).is_err());
+    }
+
+    #[test]
+    fn test_basefold_opens_sumcheck_point() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let params = BasefoldParams::new(NUM_VARS, 1, 8);
+        let polynomial = random_mle(&mut rng);
+        let claimed_sum = polynomial.sum_over_hypercube();
+        let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+
+        // the prover reduces the sum to an evaluation and opens it
+        let mut prover_transcript = Transcript::init();
+        let proof =
+            SumCheck::prove_partial(claimed_sum, &mut polynomial.clone(), &mut prover_transcript)
+                .unwrap();
+        let (evaluation, opening) = Pcs::open(
+            &params,
+            &prover_data,
+            &proof.challenges,
+            &mut prover_transcript,
+        )
+        .unwrap();
+
+        // the verifier only sees the commitment
+        let mut verify_transcript = Transcript::init();
+        let (claim, challenges) = SumCheck::<F, E, MultilinearPoly<F, E>>::verify_partial(
+            &proof,
+            NUM_VARS,
+            1,
+            &mut verify_transcript,
+        )
+        .unwrap();
+        assert_eq!(claim, evaluation);
+        let verify = Pcs::verify(
+            &params,
+            &commitment,
+            &challenges,
+            evaluation,
+            &opening,
+            &mut verify_transcript,
+        );
+        assert!(verify.unwrap());
+    }
+
+    #[test]
+    fn test_basefold_over_goldilocks() {
+        type G = Goldilocks;
+        type GE = BinomialExtensionField<G, 2>;
+
+        let polynomial = MultilinearPoly::<G, GE>::new_from_vec(
+            3,
+            (0..8)
+                .map(|val| Fields::Base(G::from_canonical_u64(val << 40)))
+                .collect(),
+        );
+        let point = (1..=3)
+            .map(|val| Fields::Extension(GE::from_canonical_u64(val)))
+            .collect::<Vec<_>>();
+        let params = BasefoldParams::new(3, 2, 8);
+
+        let (commitment, prover_data) = Basefold::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) = Basefold::open(
+            &params,
+            &prover_data,
+            &point,
+            &mut Transcript::<G, GE, KeccakChallenger64<G>>::new(),
+        )
+        .unwrap();
+        let verify = Basefold::verify(
+            &params,
+            &commitment,
+            &point,
+            evaluation,
+            &proof,
+            &mut Transcript::<G, GE, KeccakChallenger64<G>>::new(),
+        );
+        assert!(verify.unwrap());
+    }
+}
@@ -0,0 +1,261 @@
+//! Linear codes used by the commitment schemes.
+//!
+//! The random foldable code used by Basefold: level 0 encodes a single element as a repetition codeword of length 2^log_rate.
+//! Level l + 1 encodes m = (lo, hi) from level l codewords A = Enc_l(lo), B = Enc_l(hi - lo)
+//! and random non-zero weights t_l as (A + t_l ∘ B, A - t_l ∘ B).
+//! Folding a codeword with challenge r gives Enc_l(lo + r (hi - lo)), the codeword of the
+//! multilinear extension with its most significant variable fixed to r.
+use p3_field::{AbstractField, ExtensionField, Field, batch_multiplicative_inverse};
+use p3_goldilocks::Goldilocks;
+use p3_mersenne_31::Mersenne31;
+use poly::{circle::circle_lde, ntt::coset_lde};
+use rand_chacha::{
+    ChaCha20Rng,
+    rand_core::{RngCore, SeedableRng},
+};
+
+/// Fields with a quasi-linear Reed-Solomon encoder
+pub trait RsField: Field {
+    /// Low degree extension of `message`, read as evaluations over a smooth domain of its
+    /// size, to a domain 2^log_rate times larger
+    fn low_degree_extend<E: ExtensionField<Self>>(message: &[E], log_rate: usize) -> Vec<E>;
+}
+
+impl RsField for Mersenne31 {
+    fn low_degree_extend<E: ExtensionField<Self>>(message: &[E], log_rate: usize) -> Vec<E> {
+        // the circle domain needs two points, a single element extends to a repetition
+        if message.len() == 1 {
+            return vec![message[0]; 1 << log_rate];
+        }
+        circle_lde::<Self, E>(message, log_rate)
+    }
+}
+
+impl RsField for Goldilocks {
+    fn low_degree_extend<E: ExtensionField<Self>>(message: &[E], log_rate: usize) -> Vec<E> {
+        coset_lde::<Self, E>(message, Self::generator(), log_rate)
+    }
+}
+
+/// Reed-Solomon code, a message of k elements is read as the evaluations of a polynomial
+/// of degree < k over a smooth domain of size k and extended by NTT to a domain of size
+/// k · 2^log_rate, a coset of the two-adic subgroup or a circle domain for Mersenne31
+#[derive(Debug, Clone)]
+pub struct ReedSolomonCode {
+    /// Log2 of the inverse rate
+    log_rate: usize,
+}
+
+impl ReedSolomonCode {
+    pub fn new(log_rate: usize) -> Self {
+        Self { log_rate }
+    }
+
+    /// Log2 of the inverse rate
+    pub fn log_rate(&self) -> usize {
+        self.log_rate
+    }
+
+    /// Length of the codeword of a message of `message_len` elements
+    pub fn codeword_len(&self, message_len: usize) -> usize {
+        message_len << self.log_rate
+    }
+
+    /// Encodes a message of power of two length into a codeword 2^log_rate times longer
+    pub fn encode<F: RsField, E: ExtensionField<F>>(&self, message: &[E]) -> Vec<E> {
+        assert!(
+            message.len().is_power_of_two(),
+            "message length must be a power of two"
+        );
+        F::low_degree_extend(message, self.log_rate)
+    }
+}
+
+/// Random foldable code for messages of up to 2^max_log_message_len elements
+#[derive(Debug, Clone)]
+pub struct FoldableCode<F: Field> {
+    /// Log2 of the inverse rate
+    log_rate: usize,
+    /// weights[l] has 2^(log_rate + l) elements, used to encode level l + 1
+    weights: Vec<Vec<F>>,
+    /// Inverses of 2 · weights[l], used to fold level l + 1 codewords
+    fold_inverses: Vec<Vec<F>>,
+}
+
+impl<F: Field> FoldableCode<F> {
+    /// Derives the code weights from `seed`, so that prover and verifier agree on the code
+    pub fn new(max_log_message_len: usize, log_rate: usize, seed: u64) -> Self {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let weights = (0..max_log_message_len)
+            .map(|level| {
+                (0..1 << (log_rate + level))
+                    .map(|_| {
+                        loop {
+                            let weight = F::from_wrapped_u64(rng.next_u64());
+                            if !weight.is_zero() {
+                                break weight;
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let fold_inverses = weights
+            .iter()
+            .map(|level| {
+                batch_multiplicative_inverse(
+                    &level
+                        .iter()
+                        .map(|weight| weight.double())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        Self {
+            log_rate,
+            weights,
+            fold_inverses,
+        }
+    }
+
+    /// Log2 of the inverse rate
+    pub fn log_rate(&self) -> usize {
+        self.log_rate
+    }
+
+    /// Largest supported message length, as a power of two
+    pub fn max_log_message_len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Encodes a message of 2^l elements into a codeword of 2^(l + log_rate) elements
+    pub fn encode<E: ExtensionField<F>>(&self, message: &[E]) -> Vec<E> {
+        assert!(message.len().is_power_of_two());
+        let level = message.len().ilog2() as usize;
+        assert!(level <= self.max_log_message_len(), "message is too long");
+
+        if level == 0 {
+            return vec![message[0]; 1 << self.log_rate];
+        }
+        let (lo, hi) = message.split_at(message.len() / 2);
+        let diff = lo
+            .iter()
+            .zip(hi)
+            .map(|(lo, hi)| *hi - *lo)
+            .collect::<Vec<_>>();
+        let (a, b) = (self.encode(lo), self.encode(&diff));
+        let weighted = a
+            .iter()
+            .zip(&b)
+            .zip(&self.weights[level - 1])
+            .map(|((a, b), weight)| (*a, *b * *weight))
+            .collect::<Vec<_>>();
+        weighted
+            .iter()
+            .map(|(a, tb)| *a + *tb)
+            .chain(weighted.iter().map(|(a, tb)| *a - *tb))
+            .collect()
+    }
+
+    /// Folds the pair (c[index], c[index + n / 2]) of a codeword c of length n
+    pub fn fold_pair<E: ExtensionField<F>>(
+        &self,
+        codeword_len: usize,
+        index: usize,
+        pair: [E; 2],
+        challenge: E,
+    ) -> E {
+        let level = codeword_len.ilog2() as usize - self.log_rate;
+        let [left, right] = pair;
+        (left + right) * F::two().inverse()
+            + challenge * (left - right) * self.fold_inverses[level - 1][index]
+    }
+
+    /// Folds a codeword with `challenge`, halving its length
+    pub fn fold<E: ExtensionField<F>>(&self, codeword: &[E], challenge: E) -> Vec<E> {
+        let half = codeword.len() / 2;
+        (0..half)
+            .map(|index| {
+                self.fold_pair(
+                    codeword.len(),
+                    index,
+                    [codeword[index], codeword[index + half]],
+                    challenge,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks;
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{
+        Fields, MultilinearExtension, circle::CircleDomain, mle::MultilinearPoly, ntt::coset_intt,
+    };
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::{FoldableCode, ReedSolomonCode};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    #[test]
+    fn test_reed_solomon_codewords_have_low_degree() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let code = ReedSolomonCode::new(2);
+        let message = (0..8)
+            .map(|_| E::from_canonical_u32(rng.gen_range(0..1 << 30)))
+            .collect::<Vec<_>>();
+
+        let codeword = code.encode::<F, E>(&message);
+        assert_eq!(codeword.len(), code.codeword_len(8));
+        let coefficients = CircleDomain::<F>::new(5).ifft(&codeword);
+        assert!(
+            coefficients[8..]
+                .iter()
+                .all(|coeff| *coeff == AbstractField::zero())
+        );
+        assert_eq!(code.encode::<F, E>(&message[..1]), vec![message[0]; 4]);
+
+        type G = Goldilocks;
+        let message = (0..8)
+            .map(|_| G::from_canonical_u64(rng.gen_range(0..1 << 60)))
+            .collect::<Vec<_>>();
+        let mut coefficients = code.encode::<G, G>(&message);
+        coset_intt::<G, G>(&mut coefficients, G::generator());
+        assert!(
+            coefficients[8..]
+                .iter()
+                .all(|coeff| *coeff == AbstractField::zero())
+        );
+    }
+
+    #[test]
+    fn test_folding_matches_partial_evaluation() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let code = FoldableCode::<F>::new(4, 2, 0);
+        let message = (0..16)
+            .map(|_| E::from_canonical_u32(rng.gen_range(0..1 << 30)))
+            .collect::<Vec<_>>();
+        let challenge = E::from_canonical_u32(rng.gen_range(0..1 << 30));
+
+        let codeword = code.encode(&message);
+        assert_eq!(codeword.len(), 64);
+
+        let mle = MultilinearPoly::<F, E>::new_from_vec(
+            4,
+            message.iter().copied().map(Fields::Extension).collect(),
+        );
+        let folded_message = mle
+            .partial_evaluate(&[Fields::Extension(challenge)])
+            .evaluations()
+            .to_extension();
+        assert_eq!(
+            code.fold(&codeword, challenge),
+            code.encode(&folded_message)
+        );
+    }
+}
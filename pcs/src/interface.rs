@@ -0,0 +1,37 @@
+//! Interface (trait) declaration of a multilinear polynomial commitment scheme
+
+use p3_field::{ExtensionField, Field};
+use poly::{Fields, mle::MultilinearPoly};
+use transcript::{Transcript, TranscriptChallenger};
+
+pub trait PolynomialCommitmentScheme<F: Field, E: ExtensionField<F>> {
+    type Params;
+    type Commitment;
+    type ProverData;
+    type Proof;
+
+    /// Commits to a multilinear polynomial, returns the commitment and the data
+    /// the prover keeps to open it
+    fn commit(
+        params: &Self::Params,
+        polynomial: &MultilinearPoly<F, E>,
+    ) -> Result<(Self::Commitment, Self::ProverData), anyhow::Error>;
+
+    /// Opens the committed polynomial at `point`, returns the evaluation and its proof
+    fn open<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        prover_data: &Self::ProverData,
+        point: &[Fields<F, E>],
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<(E, Self::Proof), anyhow::Error>;
+
+    /// Verifies that the committed polynomial evaluates to `evaluation` at `point`
+    fn verify<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &[Fields<F, E>],
+        evaluation: E,
+        proof: &Self::Proof,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<bool, anyhow::Error>;
+}
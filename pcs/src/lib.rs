@@ -0,0 +1,6 @@
+//! Polynomial commitment schemes for multilinear polynomials.
+//! Commitments are Merkle roots over Keccak, openings are bound to the `Transcript`.
+pub mod basefold;
+pub mod code;
+pub mod interface;
+pub mod ligero;
@@ -0,0 +1,558 @@
+//! Ligero/Brakedown style tensor-code commitment scheme.
+//! The 2^n evaluations of the polynomial are arranged as a 2^(n/2) x 2^(n - n/2) matrix,
+//! with the most significant variables indexing the rows. Every row is Reed-Solomon
+//! encoded and the columns of the encoded matrix are the leaves of a Merkle tree.
+//! Since f(z) = eq(z_row)ᵀ · M · eq(z_col), an opening sends the row combination
+//! eq(z_row)ᵀ · M together with a random row combination for proximity, and opened
+//! columns check both combinations against the committed codewords.
+use std::{marker::PhantomData, ops::Mul};
+
+use anyhow::{Context, ensure};
+use p3_field::{ExtensionField, Field, PrimeField64};
+use poly::{
+    Fields, MultilinearExtension,
+    mle::{MleEvaluations, MultilinearPoly, VariableOrder},
+    utils::generate_eq,
+};
+use transcript::{Transcript, TranscriptChallenger, absorb::Bytes};
+
+use crate::{
+    code::{ReedSolomonCode, RsField},
+    interface::PolynomialCommitmentScheme,
+};
+use merkle::{KeccakDigest, KeccakHasher, MerkleProof, MerkleTree};
+
+/// Transcript label of the commitment
+const COMMITMENT_LABEL: &[u8] = b"ligero_commitment";
+/// Transcript label of the opening point
+const POINT_LABEL: &[u8] = b"ligero_point";
+/// Transcript label of the claimed evaluation
+const EVALUATION_LABEL: &[u8] = b"ligero_evaluation";
+/// Transcript label of the proximity challenges
+const PROXIMITY_LABEL: &[u8] = b"ligero_proximity_challenge";
+/// Transcript label of the combined rows
+const COMBINED_ROW_LABEL: &[u8] = b"ligero_combined_row";
+/// Transcript label preceding the column queries
+const QUERIES_LABEL: &[u8] = b"ligero_queries";
+
+/// Public parameters of the tensor-code scheme
+#[derive(Debug, Clone)]
+pub struct LigeroParams {
+    /// Number of variables of the committed polynomials
+    num_vars: usize,
+    /// Number of opened columns per opening
+    num_queries: usize,
+    /// Row code
+    code: ReedSolomonCode,
+}
+
+impl LigeroParams {
+    /// Parameters for `num_vars`-variate polynomials, rows are encoded at rate 2^-log_rate
+    pub fn new(num_vars: usize, log_rate: usize, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "ligero needs at least one query");
+        Self {
+            num_vars,
+            num_queries,
+            code: ReedSolomonCode::new(log_rate),
+        }
+    }
+
+    /// Number of variables indexing the rows of the matrix
+    pub fn row_vars(&self) -> usize {
+        self.num_vars / 2
+    }
+
+    /// Number of rows of the matrix
+    pub fn num_rows(&self) -> usize {
+        1 << self.row_vars()
+    }
+
+    /// Number of columns of the matrix before encoding
+    pub fn num_cols(&self) -> usize {
+        1 << (self.num_vars - self.row_vars())
+    }
+
+    /// Length of the encoded rows
+    pub fn codeword_len(&self) -> usize {
+        self.code.codeword_len(self.num_cols())
+    }
+}
+
+/// Commitment to a multilinear polynomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct LigeroCommitment {
+    /// Merkle root of the encoded columns
+    pub root: KeccakDigest,
+    /// Number of variables of the polynomial
+    pub num_vars: usize,
+}
+
+/// Data the prover keeps to open a commitment
+#[derive(Debug, Clone)]
+pub struct LigeroProverData<F: PrimeField64, E: ExtensionField<F>> {
+    polynomial: MultilinearPoly<F, E>,
+    /// Rows encoded in the storage field of the polynomial
+    encoded_rows: Vec<MleEvaluations<F, E>>,
+    tree: MerkleTree<F, KeccakHasher>,
+}
+
+/// Tensor-code evaluation proof
+#[derive(Debug, Clone, PartialEq)]
+pub struct LigeroProof<F: Field, E: ExtensionField<F>> {
+    /// Random combination of the rows, for proximity
+    pub proximity_row: Vec<E>,
+    /// eq(z_row) combination of the rows, for the evaluation
+    pub evaluation_row: Vec<E>,
+    /// Opened columns in the storage field of the polynomial, in query order
+    pub columns: Vec<Vec<Fields<F, E>>>,
+    /// Batch Merkle proof of the opened columns
+    pub column_proof: MerkleProof<KeccakDigest>,
+}
+
+pub struct Ligero<F: Field, E: ExtensionField<F>> {
+    _marker: PhantomData<(F, E)>,
+}
+
+impl<F: PrimeField64 + RsField, E: ExtensionField<F>> PolynomialCommitmentScheme<F, E>
+    for Ligero<F, E>
+{
+    type Params = LigeroParams;
+    type Commitment = LigeroCommitment;
+    type ProverData = LigeroProverData<F, E>;
+    type Proof = LigeroProof<F, E>;
+
+    fn commit(
+        params: &Self::Params,
+        polynomial: &MultilinearPoly<F, E>,
+    ) -> Result<(Self::Commitment, Self::ProverData), anyhow::Error> {
+        ensure!(
+            polynomial.num_vars() == params.num_vars,
+            "expected a polynomial over {} variables, got {}",
+            params.num_vars,
+            polynomial.num_vars()
+        );
+        ensure!(
+            polynomial.order() == VariableOrder::MsbFirst,
+            "only MSB-first polynomials can be committed"
+        );
+        // base field polynomials are encoded and hashed in the base field
+        let encoded_rows = match polynomial.evaluations() {
+            MleEvaluations::Base(evals) => evals
+                .chunks(params.num_cols())
+                .map(|row| MleEvaluations::Base(params.code.encode::<F, F>(row)))
+                .collect::<Vec<_>>(),
+            MleEvaluations::Extension(evals) => evals
+                .chunks(params.num_cols())
+                .map(|row| MleEvaluations::Extension(params.code.encode::<F, E>(row)))
+                .collect(),
+        };
+        let leaves = (0..params.codeword_len())
+            .map(|col| column(&encoded_rows, col))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(KeccakHasher, &leaves)?;
+
+        Ok((
+            LigeroCommitment {
+                root: tree.root(),
+                num_vars: params.num_vars,
+            },
+            LigeroProverData {
+                polynomial: polynomial.clone(),
+                encoded_rows,
+                tree,
+            },
+        ))
+    }
+
+    fn open<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        prover_data: &Self::ProverData,
+        point: &[Fields<F, E>],
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<(E, Self::Proof), anyhow::Error> {
+        ensure!(
+            point.len() == params.num_vars,
+            "expected an opening point of {} coordinates, got {}",
+            params.num_vars,
+            point.len()
+        );
+        let evaluation = prover_data.polynomial.evaluate(point).to_extension_field();
+        let commitment = LigeroCommitment {
+            root: prover_data.tree.root(),
+            num_vars: params.num_vars,
+        };
+        bind_statement(transcript, &commitment, point, evaluation);
+
+        let evaluations = prover_data.polynomial.evaluations();
+        let proximity_challenges = proximity_challenges(params, transcript);
+        let proximity_row = combine_rows(evaluations, params.num_cols(), &proximity_challenges);
+        let evaluation_row = combine_rows(evaluations, params.num_cols(), &row_eq(params, point));
+        absorb_combined_rows(transcript, &proximity_row, &evaluation_row);
+
+        let queries = sample_queries(params, transcript);
+        let columns = queries
+            .iter()
+            .map(|col| column(&prover_data.encoded_rows, *col))
+            .collect();
+        let column_proof = prover_data.tree.open_batch(&queries)?;
+
+        Ok((
+            evaluation,
+            LigeroProof {
+                proximity_row,
+                evaluation_row,
+                columns,
+                column_proof,
+            },
+        ))
+    }
+
+    fn verify<C: TranscriptChallenger<F>>(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: &[Fields<F, E>],
+        evaluation: E,
+        proof: &Self::Proof,
+        transcript: &mut Transcript<F, E, C>,
+    ) -> Result<bool, anyhow::Error> {
+        ensure!(
+            commitment.num_vars == params.num_vars && point.len() == params.num_vars,
+            "commitment and point must have {} variables",
+            params.num_vars
+        );
+        ensure!(
+            proof.proximity_row.len() == params.num_cols()
+                && proof.evaluation_row.len() == params.num_cols(),
+            "combined rows must have {} elements",
+            params.num_cols()
+        );
+        ensure!(
+            proof.columns.len() == params.num_queries,
+            "proof must open {} columns",
+            params.num_queries
+        );
+        bind_statement(transcript, commitment, point, evaluation);
+
+        let proximity_challenges = proximity_challenges(params, transcript);
+        let row_eq = row_eq(params, point);
+        absorb_combined_rows(transcript, &proof.proximity_row, &proof.evaluation_row);
+
+        let col_eq = generate_eq(&point[params.row_vars()..]);
+        let claimed = proof
+            .evaluation_row
+            .iter()
+            .zip(&col_eq)
+            .map(|(value, eq)| *value * eq.to_extension_field())
+            .sum::<E>();
+        ensure!(
+            claimed == evaluation,
+            "evaluation row does not evaluate to the claimed evaluation"
+        );
+
+        let proximity_codeword = params.code.encode::<F, E>(&proof.proximity_row);
+        let evaluation_codeword = params.code.encode::<F, E>(&proof.evaluation_row);
+        let queries = sample_queries(params, transcript);
+        ensure!(
+            proof
+                .columns
+                .iter()
+                .all(|values| values.len() == params.num_rows()),
+            "opened columns must have {} elements",
+            params.num_rows()
+        );
+        MerkleTree::verify_batch(
+            &KeccakHasher,
+            &commitment.root,
+            params.codeword_len(),
+            &queries,
+            &proof.columns,
+            &proof.column_proof,
+        )
+        .context("opened columns are not in the commitment")?;
+
+        for (col, values) in queries.into_iter().zip(&proof.columns) {
+            ensure!(
+                inner_product(values, &proximity_challenges) == proximity_codeword[col],
+                "column {col} does not match the proximity row"
+            );
+            ensure!(
+                inner_product(values, &row_eq) == evaluation_codeword[col],
+                "column {col} does not match the evaluation row"
+            );
+        }
+
+        Ok(true)
+    }
+}
+
+/// Column `col` of the encoded matrix, the Merkle leaf of that column
+fn column<F: Field, E: ExtensionField<F>>(
+    encoded_rows: &[MleEvaluations<F, E>],
+    col: usize,
+) -> Vec<Fields<F, E>> {
+    encoded_rows.iter().map(|row| row.get(col)).collect()
+}
+
+/// Σ_i coeffs[i] · rows[i] over the rows of `num_cols` elements of the evaluation table,
+/// base field rows are lifted by the coefficients only
+fn combine_rows<F: Field, E: ExtensionField<F>>(
+    evaluations: &MleEvaluations<F, E>,
+    num_cols: usize,
+    coeffs: &[E],
+) -> Vec<E> {
+    fn combine<V: Copy, E: Field + Mul<V, Output = E>>(
+        table: &[V],
+        num_cols: usize,
+        coeffs: &[E],
+    ) -> Vec<E> {
+        let mut combined = vec![E::zero(); num_cols];
+        for (row, coeff) in table.chunks(num_cols).zip(coeffs) {
+            for (acc, value) in combined.iter_mut().zip(row) {
+                *acc += *coeff * *value;
+            }
+        }
+        combined
+    }
+    match evaluations {
+        MleEvaluations::Base(table) => combine(table, num_cols, coeffs),
+        MleEvaluations::Extension(table) => combine(table, num_cols, coeffs),
+    }
+}
+
+/// Σ_i coeffs[i] · values[i]
+fn inner_product<F: Field, E: ExtensionField<F>>(values: &[Fields<F, E>], coeffs: &[E]) -> E {
+    values
+        .iter()
+        .zip(coeffs)
+        .map(|(value, coeff)| match value {
+            Fields::Base(value) => *coeff * *value,
+            Fields::Extension(value) => *coeff * *value,
+        })
+        .sum()
+}
+
+/// eq(z_row, i) for every row i
+fn row_eq<F: Field, E: ExtensionField<F>>(params: &LigeroParams, point: &[Fields<F, E>]) -> Vec<E> {
+    generate_eq(&point[..params.row_vars()])
+        .iter()
+        .map(|eq| eq.to_extension_field())
+        .collect()
+}
+
+/// Absorbs the commitment, the point and the claimed evaluation
+fn bind_statement<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    transcript: &mut Transcript<F, E, C>,
+    commitment: &LigeroCommitment,
+    point: &[Fields<F, E>],
+    evaluation: E,
+) {
+    transcript.absorb(COMMITMENT_LABEL, &Bytes(&commitment.root));
+    transcript.absorb(COMMITMENT_LABEL, &commitment.num_vars);
+    transcript.absorb(POINT_LABEL, point);
+    transcript.absorb(EVALUATION_LABEL, &Fields::<F, E>::Extension(evaluation));
+}
+
+/// Absorbs the proximity and evaluation rows
+fn absorb_combined_rows<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    transcript: &mut Transcript<F, E, C>,
+    proximity_row: &[E],
+    evaluation_row: &[E],
+) {
+    let to_fields = |row: &[E]| {
+        row.iter()
+            .copied()
+            .map(Fields::Extension)
+            .collect::<Vec<Fields<F, E>>>()
+    };
+    transcript.absorb(
+        COMBINED_ROW_LABEL,
+        &(to_fields(proximity_row), to_fields(evaluation_row)),
+    );
+}
+
+/// Samples the random row combination of the proximity test
+fn proximity_challenges<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    params: &LigeroParams,
+    transcript: &mut Transcript<F, E, C>,
+) -> Vec<E> {
+    (0..params.num_rows())
+        .map(|_| transcript.challenge(PROXIMITY_LABEL))
+        .collect()
+}
+
+/// Samples the opened columns
+fn sample_queries<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>>(
+    params: &LigeroParams,
+    transcript: &mut Transcript<F, E, C>,
+) -> Vec<usize> {
+    transcript.absorb(QUERIES_LABEL, &params.num_queries);
+    (0..params.num_queries)
+        .map(|_| transcript.sample_index(params.codeword_len()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks;
+    use p3_mersenne_31::Mersenne31 as F;
+    use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use transcript::{KeccakChallenger64, Transcript};
+
+    use super::{Ligero, LigeroParams};
+    use crate::interface::PolynomialCommitmentScheme;
+
+    type E = BinomialExtensionField<F, 3>;
+    type Pcs = Ligero<F, E>;
+
+    const NUM_VARS: usize = 5;
+
+    fn random_mle(rng: &mut StdRng) -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(
+            NUM_VARS,
+            (0..1 << NUM_VARS)
+                .map(|_| Fields::Base(F::from_canonical_u32(rng.gen_range(0..1 << 30))))
+                .collect(),
+        )
+    }
+
+    fn random_point(rng: &mut StdRng) -> Vec<Fields<F, E>> {
+        (0..NUM_VARS)
+            .map(|_| Fields::Extension(E::from_canonical_u32(rng.gen_range(0..1 << 30))))
+            .collect()
+    }
+
+    #[test]
+    fn test_ligero_open_and_verify() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let params = LigeroParams::new(NUM_VARS, 2, 16);
+        assert_eq!((params.num_rows(), params.num_cols()), (4, 8));
+        let polynomial = random_mle(&mut rng);
+        let point = random_point(&mut rng);
+
+        let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) =
+            Pcs::open(&params, &prover_data, &point, &mut Transcript::init()).unwrap();
+        assert_eq!(evaluation, polynomial.evaluate(&point).to_extension_field());
+
+        let verify = Pcs::verify(
+            &params,
+            &commitment,
+            &point,
+            evaluation,
+            &proof,
+            &mut Transcript::init(),
+        );
+        assert!(verify.unwrap());
+    }
+
+    #[test]
+    fn test_ligero_rejects_tampering() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let params = LigeroParams::new(NUM_VARS, 2, 16);
+        let polynomial = random_mle(&mut rng);
+        let point = random_point(&mut rng);
+        let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) =
+            Pcs::open(&params, &prover_data, &point, &mut Transcript::init()).unwrap();
+
+        let verify = |commitment, evaluation, proof| {
+            Pcs::verify(
+                &params,
+                commitment,
+                &point,
+                evaluation,
+                proof,
+                &mut Transcript::init(),
+            )
+        };
+
+        assert!(verify(&commitment, evaluation + E::one(), &proof).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.columns[3][1] =
+            Fields::Base(tampered.columns[3][1].to_base_field().unwrap() + F::one());
+        assert!(verify(&commitment, evaluation, &tampered).is_err());
+
+        // the same value lifted to the extension field is a different leaf
+        let mut tampered = proof.clone();
+        tampered.columns[3][1] = Fields::Extension(tampered.columns[3][1].to_extension_field());
+        assert!(verify(&commitment, evaluation, &tampered).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.proximity_row[0] += E::one();
+        assert!(verify(&commitment, evaluation, &tampered).is_err());
+
+        let (other_commitment, _) = Pcs::commit(&params, &random_mle(&mut rng)).unwrap();
+        assert!(verify(&other_commitment, evaluation, &proof).is_err());
+    }
+
+    #[test]
+    fn test_ligero_commits_in_the_storage_field() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let params = LigeroParams::new(NUM_VARS, 1, 8);
+        let point = random_point(&mut rng);
+        let base = random_mle(&mut rng);
+        let extension =
+            MultilinearPoly::<F, E>::from_extension(NUM_VARS, base.evaluations().to_extension());
+
+        for (polynomial, is_base) in [(base, true), (extension, false)] {
+            let (commitment, prover_data) = Pcs::commit(&params, &polynomial).unwrap();
+            let (evaluation, proof) =
+                Pcs::open(&params, &prover_data, &point, &mut Transcript::init()).unwrap();
+            assert!(
+                proof
+                    .columns
+                    .iter()
+                    .flatten()
+                    .all(|value| value.is_base_field() == is_base)
+            );
+            let verify = Pcs::verify(
+                &params,
+                &commitment,
+                &point,
+                evaluation,
+                &proof,
+                &mut Transcript::init(),
+            );
+            assert!(verify.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_ligero_over_goldilocks() {
+        type G = Goldilocks;
+        type GE = BinomialExtensionField<G, 2>;
+
+        let polynomial = MultilinearPoly::<G, GE>::new_from_vec(
+            4,
+            (0..16)
+                .map(|val| Fields::Base(G::from_canonical_u64(val << 40)))
+                .collect(),
+        );
+        let point = (1..=4)
+            .map(|val| Fields::Extension(GE::from_canonical_u64(val)))
+            .collect::<Vec<_>>();
+        let params = LigeroParams::new(4, 1, 8);
+
+        let (commitment, prover_data) = Ligero::commit(&params, &polynomial).unwrap();
+        let (evaluation, proof) = Ligero::open(
+            &params,
+            &prover_data,
+            &point,
+            &mut Transcript::<G, GE, KeccakChallenger64<G>>::new(),
+        )
+        .unwrap();
+        assert_eq!(evaluation, polynomial.evaluate(&point).to_extension_field());
+        let verify = Ligero::verify(
+            &params,
+            &commitment,
+            &point,
+            evaluation,
+            &proof,
+            &mut Transcript::<G, GE, KeccakChallenger64<G>>::new(),
+        );
+        assert!(verify.unwrap());
+    }
+}
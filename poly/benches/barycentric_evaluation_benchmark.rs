@@ -1,7 +1,7 @@
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use p3_field::extension::BinomialExtensionField;
 use p3_mersenne_31::Mersenne31;
-use poly::{Fields, utils::barycentric_evaluation};
+use poly::{Fields, barycentric::BarycentricDomain, utils::barycentric_evaluation};
 
 type F = Mersenne31;
 type E = BinomialExtensionField<Mersenne31, 3>;
@@ -40,6 +40,21 @@ fn barycentric_evaluation_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, barycentric_evaluation_benchmark,);
+fn barycentric_domain_benchmark(c: &mut Criterion) {
+    let domain = BarycentricDomain::<F, E>::integers(20);
+    let poly_evaluation = (0..20)
+        .map(|i| Fields::Base(F::new(7919 * i + 428910)))
+        .collect::<Vec<_>>();
+
+    c.bench_function("barycentric_domain_evaluation", |b| {
+        b.iter(|| black_box(domain.evaluate(&poly_evaluation, &Fields::Base(F::new(100)))))
+    });
+}
+
+criterion_group!(
+    benches,
+    barycentric_evaluation_benchmark,
+    barycentric_domain_benchmark,
+);
 
 criterion_main!(benches);
@@ -6,12 +6,19 @@ use multilinear_extensions::mle::{
 use p3::goldilocks::Goldilocks as CenoGoldilocks;
 use p3_field::{AbstractField, extension::BinomialExtensionField};
 use p3_goldilocks::Goldilocks;
-use poly::{Fields, MultilinearExtension, mle::MultilinearPoly};
+use poly::{Fields, MultilinearExtension, mle::MultilinearPoly, utils::generate_eq};
 use rand::thread_rng;
 
 type F = Goldilocks;
 type E = BinomialExtensionField<Goldilocks, 2>;
 
+/// Suffix used to tell serial and `parallel` feature runs apart
+const MODE: &str = if cfg!(feature = "parallel") {
+    "parallel"
+} else {
+    "serial"
+};
+
 fn n_points(n: usize) -> Vec<GoldilocksExt2> {
     let mut rng = thread_rng();
     (0..n)
@@ -54,10 +61,13 @@ fn random_dense_poly_with_eval_slcore(
 
 fn bench_slcore_against_ceno(c: &mut Criterion) {
     // 20 vars, 1 eval
-    c.bench_function("slcore_fix_variables_20_vars_1_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(20, 1);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_20_vars_1_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(20, 1);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_20_vars_1_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(20, 1);
@@ -76,10 +86,13 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 
     // 20 vars, 10 eval
-    c.bench_function("slcore_fix_variables_20_vars_10_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(20, 10);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_20_vars_10_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(20, 10);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_20_vars_10_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(20, 10);
@@ -98,10 +111,13 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 
     // 23 vars, 1 eval
-    c.bench_function("slcore_fix_variables_23_vars_1_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(23, 1);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_23_vars_1_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(23, 1);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_23_vars_1_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(23, 1);
@@ -120,10 +136,13 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 
     // 23 vars, 10 evals
-    c.bench_function("slcore_fix_variables_23_vars_10_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(23, 10);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_23_vars_10_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(23, 10);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_23_vars_10_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(23, 10);
@@ -142,10 +161,13 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 
     // 25 vars, 1 eval
-    c.bench_function("slcore_fix_variables_25_vars_1_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(25, 1);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_25_vars_1_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(25, 1);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_25_vars_1_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(25, 1);
@@ -164,10 +186,13 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 
     // 25 vars, 10 eval
-    c.bench_function("slcore_fix_variables_25_vars_10_eval", |b| {
-        let (poly, to_eval) = random_dense_poly_with_eval_slcore(25, 10);
-        b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
-    });
+    c.bench_function(
+        &format!("slcore_fix_variables_25_vars_10_eval_{MODE}"),
+        |b| {
+            let (poly, to_eval) = random_dense_poly_with_eval_slcore(25, 10);
+            b.iter(|| poly.partial_evaluate(to_eval.as_slice()))
+        },
+    );
 
     c.bench_function("ceno_fix_variables_25_vars_10_eval", |b| {
         let (poly, to_eval) = random_dense_poly_with_eval(25, 10);
@@ -186,5 +211,26 @@ fn bench_slcore_against_ceno(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, bench_slcore_against_ceno);
+fn bench_slcore_hypercube_ops(c: &mut Criterion) {
+    for nv in [16, 20, 23] {
+        c.bench_function(
+            &format!("slcore_sum_over_hypercube_{nv}_vars_{MODE}"),
+            |b| {
+                let (poly, _) = random_dense_poly_with_eval_slcore(nv, 0);
+                b.iter(|| poly.sum_over_hypercube())
+            },
+        );
+
+        c.bench_function(&format!("slcore_generate_eq_{nv}_vars_{MODE}"), |b| {
+            let points = n_points_slcore(nv);
+            b.iter(|| generate_eq(&points))
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_slcore_against_ceno,
+    bench_slcore_hypercube_ops
+);
 criterion_main!(benches);
@@ -0,0 +1,179 @@
+//! Barycentric interpolation over a fixed set of points.
+//! The weights w_i = 1 / Π_{j != i} (x_i - x_j) are computed once per domain, after which
+//! p(x) = M(x) · Σ_i w_i · p(x_i) / (x - x_i) with M(x) = Π_i (x - x_i) costs O(n)
+//! multiplications and a single batched inversion.
+use p3_field::{ExtensionField, Field, batch_multiplicative_inverse};
+
+use crate::Fields;
+
+/// Evaluation domain with precomputed barycentric weights
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarycentricDomain<F: Field, E: ExtensionField<F>> {
+    points: Vec<Fields<F, E>>,
+    weights: Vec<E>,
+}
+
+impl<F: Field, E: ExtensionField<F>> BarycentricDomain<F, E> {
+    /// Domain over arbitrary distinct points, O(n²) to build
+    pub fn new(points: Vec<Fields<F, E>>) -> Self {
+        let values = points
+            .iter()
+            .map(|point| point.to_extension_field())
+            .collect::<Vec<_>>();
+        let denominators = values
+            .iter()
+            .enumerate()
+            .map(|(i, x_i)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(E::one(), |acc, (_, x_j)| acc * (*x_i - *x_j))
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            denominators
+                .iter()
+                .all(|denominator| !denominator.is_zero()),
+            "domain points must be distinct"
+        );
+
+        Self {
+            points,
+            weights: batch_multiplicative_inverse(&denominators),
+        }
+    }
+
+    /// The domain {0, 1, ..., n - 1}, whose weights (-1)^(n-1-i) / (i! (n-1-i)!) take O(n)
+    pub fn integers(n: usize) -> Self {
+        let mut factorials = vec![F::one(); n.max(1)];
+        for i in 1..n {
+            factorials[i] = factorials[i - 1] * F::from_canonical_usize(i);
+        }
+        let denominators = (0..n)
+            .map(|i| {
+                let denominator = factorials[i] * factorials[n - 1 - i];
+                if (n - 1 - i).is_multiple_of(2) {
+                    denominator
+                } else {
+                    -denominator
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            points: (0..n)
+                .map(|i| Fields::Base(F::from_canonical_usize(i)))
+                .collect(),
+            weights: batch_multiplicative_inverse(&denominators)
+                .into_iter()
+                .map(E::from_base)
+                .collect(),
+        }
+    }
+
+    pub fn points(&self) -> &[Fields<F, E>] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Every Lagrange basis polynomial L_i(x) = M(x) · w_i / (x - x_i) at `point`
+    pub fn lagrange_basis(&self, point: &Fields<F, E>) -> Vec<Fields<F, E>> {
+        let x = point.to_extension_field();
+        let differences = self
+            .points
+            .iter()
+            .map(|x_i| x - x_i.to_extension_field())
+            .collect::<Vec<_>>();
+
+        // the basis is an indicator when the point is in the domain
+        if let Some(i) = differences.iter().position(|diff| diff.is_zero()) {
+            return (0..self.len())
+                .map(|j| Fields::Base(if i == j { F::one() } else { F::zero() }))
+                .collect();
+        }
+
+        let vanishing = differences.iter().copied().product::<E>();
+        batch_multiplicative_inverse(&differences)
+            .into_iter()
+            .zip(&self.weights)
+            .map(|(inverse, weight)| Fields::Extension(vanishing * *weight * inverse))
+            .collect()
+    }
+
+    /// Evaluates the polynomial of degree < n taking `evaluations` over the domain
+    pub fn evaluate(&self, evaluations: &[Fields<F, E>], point: &Fields<F, E>) -> Fields<F, E> {
+        assert_eq!(evaluations.len(), self.len());
+        let res = self
+            .lagrange_basis(point)
+            .into_iter()
+            .zip(evaluations)
+            .fold(E::zero(), |acc, (basis, eval)| {
+                acc + basis.to_extension_field() * eval.to_extension_field()
+            });
+        Fields::Extension(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use super::BarycentricDomain;
+    use crate::Fields;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn fields(values: &[u32]) -> Vec<Fields<F, E>> {
+        values
+            .iter()
+            .map(|val| Fields::Base(F::from_canonical_u32(*val)))
+            .collect()
+    }
+
+    #[test]
+    fn test_integer_weights_match_generic_weights() {
+        for n in 1..8 {
+            let points = (0..n).collect::<Vec<_>>();
+            assert_eq!(
+                BarycentricDomain::<F, E>::integers(n as usize),
+                BarycentricDomain::new(fields(&points))
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_over_domain() {
+        // 5x^2 + 3x + 2 over {1, 4, 9} and over {0, 1, 2}
+        let point = Fields::Extension(E::from_canonical_u32(5));
+        let domain = BarycentricDomain::new(fields(&[1, 4, 9]));
+        assert_eq!(
+            domain.evaluate(&fields(&[10, 94, 434]), &point),
+            Fields::Extension(E::from_canonical_u32(142))
+        );
+        let integers = BarycentricDomain::integers(3);
+        assert_eq!(
+            integers.evaluate(&fields(&[2, 10, 28]), &point),
+            Fields::Extension(E::from_canonical_u32(142))
+        );
+
+        // points of the domain read the evaluation back
+        assert_eq!(
+            domain
+                .evaluate(
+                    &fields(&[10, 94, 434]),
+                    &Fields::Base(F::from_canonical_u32(4))
+                )
+                .to_extension_field(),
+            E::from_canonical_u32(94)
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Circle group FFT, for fields such as Mersenne31 whose multiplicative group is not
+//! two-adic but whose circle x² + y² = 1 has order p + 1 = 2^31.
+//! Polynomials are expressed over the basis b_j = y^(j_0) · x^(j_1) · π(x)^(j_2) · π²(x)^(j_3) ..
+//! with j = Σ j_k 2^k and π(x) = 2x² - 1 the x-coordinate of the doubling map.
+//! Points are elements of the complex extension, x being the real part and y the imaginary one.
+use std::marker::PhantomData;
+
+use p3_field::{
+    AbstractField, ExtensionField, Field, batch_multiplicative_inverse,
+    extension::{Complex, ComplexExtendable},
+};
+
+/// The standard position coset Q · <Q²> of 2^log_n circle points, Q of order 2^(log_n + 1).
+/// The i'th point is P_i = Q^(2i + 1) and its conjugate (x, -y) is P_(n - 1 - i).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircleDomain<F> {
+    log_n: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: ComplexExtendable> CircleDomain<F> {
+    pub fn new(log_n: usize) -> Self {
+        assert!(log_n > 0, "circle domain needs at least two points");
+        assert!(
+            log_n < F::CIRCLE_TWO_ADICITY,
+            "circle domain exceeds the circle two-adicity"
+        );
+        Self {
+            log_n,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn log_size(&self) -> usize {
+        self.log_n
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_n
+    }
+
+    /// Points of the domain in natural order
+    pub fn points(&self) -> Vec<Complex<F>> {
+        let q = F::circle_two_adic_generator(self.log_n + 1);
+        q.shifted_powers(q).step_by(2).take(self.size()).collect()
+    }
+
+    /// Coefficients to evaluations over the domain
+    pub fn fft<E: ExtensionField<F>>(&self, coefficients: &[E]) -> Vec<E> {
+        assert_eq!(coefficients.len(), self.size());
+        let points = self.points();
+        let half = self.size() / 2;
+        let (xs, ys): (Vec<_>, Vec<_>) = points[..half]
+            .iter()
+            .map(|point| (point.real(), point.imag()))
+            .unzip();
+
+        let (even, odd) = split_even_odd(coefficients);
+        let (even, odd) = (fft_x(&even, &xs), fft_x(&odd, &xs));
+
+        let mut evaluations = vec![E::zero(); self.size()];
+        for i in 0..half {
+            let odd = odd[i] * ys[i];
+            evaluations[i] = even[i] + odd;
+            evaluations[self.size() - 1 - i] = even[i] - odd;
+        }
+        evaluations
+    }
+
+    /// Evaluations over the domain to coefficients
+    pub fn ifft<E: ExtensionField<F>>(&self, evaluations: &[E]) -> Vec<E> {
+        assert_eq!(evaluations.len(), self.size());
+        let points = self.points();
+        let half = self.size() / 2;
+        let xs = points[..half]
+            .iter()
+            .map(|point| point.real())
+            .collect::<Vec<_>>();
+        let y_inverses = batch_multiplicative_inverse(
+            &points[..half]
+                .iter()
+                .map(|point| point.imag())
+                .collect::<Vec<_>>(),
+        );
+
+        // f(x, y) = f_0(x) + y · f_1(x)
+        let half_inv = F::two().inverse();
+        let (even, odd): (Vec<_>, Vec<_>) = (0..half)
+            .map(|i| {
+                let (lhs, rhs) = (evaluations[i], evaluations[self.size() - 1 - i]);
+                (
+                    (lhs + rhs) * half_inv,
+                    (lhs - rhs) * (half_inv * y_inverses[i]),
+                )
+            })
+            .unzip();
+        interleave(ifft_x(&even, &xs), ifft_x(&odd, &xs))
+    }
+}
+
+/// Low degree extension of evaluations over the domain of their size to the domain
+/// 2^log_blowup times larger
+pub fn circle_lde<F: ComplexExtendable, E: ExtensionField<F>>(
+    evaluations: &[E],
+    log_blowup: usize,
+) -> Vec<E> {
+    assert!(evaluations.len().is_power_of_two());
+    let log_n = evaluations.len().ilog2() as usize;
+    let mut coefficients = CircleDomain::<F>::new(log_n).ifft(evaluations);
+    coefficients.resize(evaluations.len() << log_blowup, E::zero());
+    CircleDomain::<F>::new(log_n + log_blowup).fft(&coefficients)
+}
+
+/// Evaluates coefficients over the circle basis at any point of the circle
+pub fn evaluate_at_point<F: ComplexExtendable, E: ExtensionField<F>>(
+    coefficients: &[E],
+    point: Complex<F>,
+) -> E {
+    // x-factors x, π(x), π²(x), .. of the basis
+    let mut factors = vec![point.real()];
+    while 4 << factors.len() <= coefficients.len() {
+        let x = factors[factors.len() - 1];
+        factors.push(x.square().double() - F::one());
+    }
+
+    coefficients
+        .iter()
+        .enumerate()
+        .fold(E::zero(), |acc, (j, coeff)| {
+            let y_part = if j & 1 == 1 { point.imag() } else { F::one() };
+            let basis = factors
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| (j >> (k + 1)) & 1 == 1)
+                .fold(y_part, |acc, (_, factor)| acc * *factor);
+            acc + *coeff * basis
+        })
+}
+
+/// Evaluates coefficients over the x-factor basis at the x-coordinates of a layer,
+/// whose i'th and (m - 1 - i)'th coordinates are opposite
+fn fft_x<F: Field, E: ExtensionField<F>>(coefficients: &[E], xs: &[F]) -> Vec<E> {
+    let m = coefficients.len();
+    if m == 1 {
+        return coefficients.to_vec();
+    }
+    let next_xs = xs[..m / 2]
+        .iter()
+        .map(|x| x.square().double() - F::one())
+        .collect::<Vec<_>>();
+    let (even, odd) = split_even_odd(coefficients);
+    let (even, odd) = (fft_x(&even, &next_xs), fft_x(&odd, &next_xs));
+
+    let mut evaluations = vec![E::zero(); m];
+    for i in 0..m / 2 {
+        let odd = odd[i] * xs[i];
+        evaluations[i] = even[i] + odd;
+        evaluations[m - 1 - i] = even[i] - odd;
+    }
+    evaluations
+}
+
+fn ifft_x<F: Field, E: ExtensionField<F>>(evaluations: &[E], xs: &[F]) -> Vec<E> {
+    let m = evaluations.len();
+    if m == 1 {
+        return evaluations.to_vec();
+    }
+    let x_inverses = batch_multiplicative_inverse(&xs[..m / 2]);
+    let next_xs = xs[..m / 2]
+        .iter()
+        .map(|x| x.square().double() - F::one())
+        .collect::<Vec<_>>();
+
+    // f(x) = f_0(π(x)) + x · f_1(π(x))
+    let half_inv = F::two().inverse();
+    let (even, odd): (Vec<_>, Vec<_>) = (0..m / 2)
+        .map(|i| {
+            let (lhs, rhs) = (evaluations[i], evaluations[m - 1 - i]);
+            (
+                (lhs + rhs) * half_inv,
+                (lhs - rhs) * (half_inv * x_inverses[i]),
+            )
+        })
+        .unzip();
+    interleave(ifft_x(&even, &next_xs), ifft_x(&odd, &next_xs))
+}
+
+fn split_even_odd<E: Copy>(values: &[E]) -> (Vec<E>, Vec<E>) {
+    (
+        values.iter().step_by(2).copied().collect(),
+        values.iter().skip(1).step_by(2).copied().collect(),
+    )
+}
+
+fn interleave<E: Copy>(even: Vec<E>, odd: Vec<E>) -> Vec<E> {
+    even.into_iter()
+        .zip(odd)
+        .flat_map(|(even, odd)| [even, odd])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::{CircleDomain, circle_lde, evaluate_at_point};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn random_values(rng: &mut StdRng, len: usize) -> Vec<E> {
+        (0..len)
+            .map(|_| E::from_canonical_u32(rng.gen_range(0..1 << 30)))
+            .collect()
+    }
+
+    #[test]
+    fn test_domain_points() {
+        let domain = CircleDomain::<F>::new(4);
+        let points = domain.points();
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(point.real().square() + point.imag().square(), F::one());
+            assert_eq!(points[domain.size() - 1 - i], point.conjugate());
+        }
+    }
+
+    #[test]
+    fn test_fft_against_naive_evaluation() {
+        let mut rng = StdRng::seed_from_u64(47);
+        for log_n in 1..7 {
+            let domain = CircleDomain::<F>::new(log_n);
+            let coefficients = random_values(&mut rng, domain.size());
+
+            let evaluations = domain.fft(&coefficients);
+            let naive = domain
+                .points()
+                .into_iter()
+                .map(|point| evaluate_at_point(&coefficients, point))
+                .collect::<Vec<_>>();
+            assert_eq!(evaluations, naive);
+            assert_eq!(domain.ifft(&evaluations), coefficients);
+        }
+    }
+
+    #[test]
+    fn test_circle_lde() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let domain = CircleDomain::<F>::new(3);
+        let coefficients = random_values(&mut rng, domain.size());
+
+        let extended = circle_lde::<F, E>(&domain.fft(&coefficients), 2);
+        let naive = CircleDomain::<F>::new(5)
+            .points()
+            .into_iter()
+            .map(|point| evaluate_at_point(&coefficients, point))
+            .collect::<Vec<_>>();
+        assert_eq!(extended, naive);
+    }
+}
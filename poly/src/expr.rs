@@ -0,0 +1,341 @@
+//! Symbolic combine expressions for virtual polynomials.
+//! An `Expr` describes how the MLEs of a `VPoly` are combined, which lets the
+//! virtual polynomial infer its degree, bind the combination to a transcript and
+//! compile it to a flat evaluator.
+use std::{
+    ops::{Add, Mul},
+    sync::Arc,
+};
+
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
+use crate::{Fields, vpoly::CombineFn};
+
+/// Expression over the MLEs of a virtual polynomial
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr<F: Field, E: ExtensionField<F>> {
+    /// Constant field element
+    Constant(Fields<F, E>),
+    /// Reference to the MLE at the given index
+    Mle(usize),
+    /// Sum of sub-expressions
+    Sum(Vec<Expr<F, E>>),
+    /// Product of sub-expressions
+    Product(Vec<Expr<F, E>>),
+}
+
+impl<F: Field, E: ExtensionField<F>> Expr<F, E> {
+    /// Expression referencing the MLE at `index`
+    pub fn mle(index: usize) -> Self {
+        Expr::Mle(index)
+    }
+
+    /// Constant expression
+    pub fn constant(value: Fields<F, E>) -> Self {
+        Expr::Constant(value)
+    }
+
+    /// Max variable degree of the expression, i.e. the max number of MLEs
+    /// multiplied together in any term
+    pub fn degree(&self) -> usize {
+        match self {
+            Expr::Constant(_) => 0,
+            Expr::Mle(_) => 1,
+            Expr::Sum(terms) => terms.iter().map(Expr::degree).max().unwrap_or(0),
+            Expr::Product(factors) => factors.iter().map(Expr::degree).sum(),
+        }
+    }
+
+    /// Number of MLEs the expression expects, one more than the largest referenced index
+    pub fn num_mles(&self) -> usize {
+        match self {
+            Expr::Constant(_) => 0,
+            Expr::Mle(index) => index + 1,
+            Expr::Sum(children) | Expr::Product(children) => {
+                children.iter().map(Expr::num_mles).max().unwrap_or(0)
+            }
+        }
+    }
+
+    /// Evaluates the expression by walking the tree, given the MLE evaluations
+    pub fn evaluate(&self, values: &[Fields<F, E>]) -> Fields<F, E> {
+        match self {
+            Expr::Constant(value) => *value,
+            Expr::Mle(index) => values[*index],
+            Expr::Sum(terms) => terms.iter().fold(Fields::Base(F::zero()), |acc, term| {
+                acc + term.evaluate(values)
+            }),
+            Expr::Product(factors) => factors
+                .iter()
+                .map(|factor| factor.evaluate(values))
+                .product(),
+        }
+    }
+
+    /// Flattens nested sums and products and folds constants together
+    pub fn simplify(&self) -> Self {
+        match self {
+            Expr::Constant(_) | Expr::Mle(_) => self.clone(),
+            Expr::Sum(terms) => {
+                let mut constant = Fields::Base(F::zero());
+                let mut flattened = vec![];
+                for term in terms.iter().map(Expr::simplify) {
+                    let terms = match term {
+                        Expr::Sum(inner) => inner,
+                        term => vec![term],
+                    };
+                    for term in terms {
+                        match term {
+                            Expr::Constant(value) => constant += value,
+                            term => flattened.push(term),
+                        }
+                    }
+                }
+                if !constant.to_extension_field().is_zero() || flattened.is_empty() {
+                    flattened.push(Expr::Constant(constant));
+                }
+                if flattened.len() == 1 {
+                    flattened.pop().unwrap()
+                } else {
+                    Expr::Sum(flattened)
+                }
+            }
+            Expr::Product(factors) => {
+                let mut constant = Fields::Base(F::one());
+                let mut flattened = vec![];
+                for factor in factors.iter().map(Expr::simplify) {
+                    let factors = match factor {
+                        Expr::Product(inner) => inner,
+                        factor => vec![factor],
+                    };
+                    for factor in factors {
+                        match factor {
+                            Expr::Constant(value) => constant = constant * value,
+                            factor => flattened.push(factor),
+                        }
+                    }
+                }
+                if constant.to_extension_field().is_zero() {
+                    return Expr::Constant(constant);
+                }
+                if !constant.to_extension_field().is_one() || flattened.is_empty() {
+                    flattened.insert(0, Expr::Constant(constant));
+                }
+                if flattened.len() == 1 {
+                    flattened.pop().unwrap()
+                } else {
+                    Expr::Product(flattened)
+                }
+            }
+        }
+    }
+
+    /// Compiles the expression into a flat stack program
+    pub fn compile(&self) -> CompiledExpr<F, E> {
+        let mut program = vec![];
+        self.simplify().emit(&mut program);
+        CompiledExpr { program }
+    }
+
+    /// Compiles the expression into a `VPoly` combine function
+    pub fn to_combine_fn(&self) -> CombineFn<F, E> {
+        let compiled = self.compile();
+        Arc::new(move |values: &[Fields<F, E>]| compiled.evaluate(values))
+    }
+
+    /// Absorbs a prefix encoding of the expression into the transcript
+    pub fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.absorb_into(transcript);
+    }
+
+    /// Appends the postfix instructions for the expression
+    fn emit(&self, program: &mut Vec<Instruction<F, E>>) {
+        match self {
+            Expr::Constant(value) => program.push(Instruction::Constant(*value)),
+            Expr::Mle(index) => program.push(Instruction::Mle(*index)),
+            Expr::Sum(terms) => {
+                terms.iter().for_each(|term| term.emit(program));
+                program.push(Instruction::Add(terms.len()));
+            }
+            Expr::Product(factors) => {
+                factors.iter().for_each(|factor| factor.emit(program));
+                program.push(Instruction::Mul(factors.len()));
+            }
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Add for Expr<F, E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Expr::Sum(vec![self, rhs])
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Mul for Expr<F, E> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Expr::Product(vec![self, rhs])
+    }
+}
+
+/// Instruction of a compiled expression
+#[derive(Debug, Clone)]
+enum Instruction<F: Field, E: ExtensionField<F>> {
+    /// Push a constant
+    Constant(Fields<F, E>),
+    /// Push the evaluation of an MLE
+    Mle(usize),
+    /// Pop n values and push their sum
+    Add(usize),
+    /// Pop n values and push their product
+    Mul(usize),
+}
+
+/// Expression compiled to a postfix program, evaluated without recursion
+#[derive(Debug, Clone)]
+pub struct CompiledExpr<F: Field, E: ExtensionField<F>> {
+    program: Vec<Instruction<F, E>>,
+}
+
+impl<F: Field, E: ExtensionField<F>> CompiledExpr<F, E> {
+    /// Evaluates the program given the MLE evaluations
+    pub fn evaluate(&self, values: &[Fields<F, E>]) -> Fields<F, E> {
+        let mut stack = Vec::with_capacity(self.program.len());
+        for instruction in &self.program {
+            match instruction {
+                Instruction::Constant(value) => stack.push(*value),
+                Instruction::Mle(index) => stack.push(values[*index]),
+                Instruction::Add(n) => {
+                    let at = stack.len() - n;
+                    let sum = stack
+                        .drain(at..)
+                        .fold(Fields::Base(F::zero()), |acc, val| acc + val);
+                    stack.push(sum);
+                }
+                Instruction::Mul(n) => {
+                    let at = stack.len() - n;
+                    let product = stack.drain(at..).product();
+                    stack.push(product);
+                }
+            }
+        }
+        stack
+            .pop()
+            .expect("compiled expression leaves a single value")
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for Expr<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        match self {
+            Expr::Constant(value) => {
+                0_usize.absorb_into(transcript);
+                value.absorb_into(transcript);
+            }
+            Expr::Mle(index) => {
+                1_usize.absorb_into(transcript);
+                index.absorb_into(transcript);
+            }
+            Expr::Sum(children) => {
+                2_usize.absorb_into(transcript);
+                children.absorb_into(transcript);
+            }
+            Expr::Product(children) => {
+                3_usize.absorb_into(transcript);
+                children.absorb_into(transcript);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn constant(val: u32) -> Expr<F, E> {
+        Expr::constant(Fields::Base(F::from_canonical_u32(val)))
+    }
+
+    fn to_fields(vals: Vec<u32>) -> Vec<Fields<F, E>> {
+        vals.into_iter()
+            .map(|v| Fields::Base(F::from_canonical_u32(v)))
+            .collect()
+    }
+
+    // 2 * m0 * m1 + m2
+    fn expr() -> Expr<F, E> {
+        constant(2) * Expr::mle(0) * Expr::mle(1) + Expr::mle(2)
+    }
+
+    #[test]
+    fn test_degree_and_num_mles() {
+        assert_eq!(expr().degree(), 2);
+        assert_eq!(expr().num_mles(), 3);
+        assert_eq!(constant(5).degree(), 0);
+        assert_eq!(
+            (Expr::<F, E>::mle(4) * Expr::mle(4) * Expr::mle(1)).degree(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_simplify() {
+        let simplified = expr().simplify();
+        assert_eq!(
+            simplified,
+            Expr::Sum(vec![
+                Expr::Product(vec![constant(2), Expr::mle(0), Expr::mle(1)]),
+                Expr::mle(2)
+            ])
+        );
+
+        // constants fold, and a zero factor collapses the product
+        assert_eq!((constant(2) + constant(3)).simplify(), constant(5));
+        assert_eq!(
+            (constant(0) * Expr::mle(0)).simplify(),
+            Expr::Constant(Fields::Base(F::zero()))
+        );
+    }
+
+    #[test]
+    fn test_compiled_matches_tree_evaluation() {
+        let values = to_fields(vec![3, 4, 5]);
+        // 2 * 3 * 4 + 5 = 29
+        assert_eq!(
+            expr().evaluate(&values),
+            Fields::Base(F::from_canonical_u32(29))
+        );
+        assert_eq!(expr().compile().evaluate(&values), expr().evaluate(&values));
+        assert_eq!((expr().to_combine_fn())(&values), expr().evaluate(&values));
+    }
+
+    #[test]
+    fn test_transcript_binds_expression() {
+        let challenge = |expr: Expr<F, E>| {
+            let mut transcript = Transcript::<F, E>::init();
+            expr.commit_to_transcript(&mut transcript);
+            transcript.sample_challenge()
+        };
+
+        assert_eq!(challenge(expr()), challenge(expr()));
+        assert_ne!(
+            challenge(expr()),
+            challenge(constant(3) * Expr::mle(0) * Expr::mle(1) + Expr::mle(2))
+        );
+        assert_ne!(
+            challenge(Expr::mle(0) + Expr::mle(1)),
+            challenge(Expr::mle(0) * Expr::mle(1))
+        );
+    }
+}
@@ -1,16 +1,38 @@
 pub use fields::Fields;
-use p3_field::{ExtensionField, Field, PrimeField32};
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger};
 
+pub mod barycentric;
+pub mod circle;
+pub mod expr;
 pub mod mle;
+pub mod ntt;
+pub mod sop;
+pub mod sparse_mle;
+pub mod univariate;
 pub mod utils;
 pub mod vpoly;
 
+/// Minimum number of evaluations a loop has to cover before it is split across threads
+#[cfg(feature = "parallel")]
+pub const PARALLEL_THRESHOLD: usize = 1 << 12;
+
 /// Multilinear Extension Trait
 pub trait MultilinearExtension<F: Field, E: ExtensionField<F>> {
     /// Fix all variables
     fn evaluate(&self, point: &[Fields<F, E>]) -> Fields<F, E>;
     /// Partially fix variables starting from the first
     fn partial_evaluate(&self, point: &[Fields<F, E>]) -> Self;
+    /// Fix the first variable in place, reusing the current storage where possible
+    fn fix_first_var_in_place(&mut self, point: &Fields<F, E>)
+    where
+        Self: Sized,
+    {
+        *self = self.partial_evaluate(&[*point]);
+    }
+    /// Collapse the first log2(weights.len()) variables into the weighted sum
+    /// f'(x) = Σ_h weights[h] · f(h, x), partial evaluation uses eq weights
+    fn fold_first_vars(&self, weights: &[Fields<F, E>]) -> Self;
     /// Returns the max variable degree
     fn max_degree(&self) -> usize;
     /// Returns the sum of evaluations over the boolean hypercube
@@ -18,7 +40,8 @@ pub trait MultilinearExtension<F: Field, E: ExtensionField<F>> {
     /// Returns the number of variables of the polynomial
     fn num_vars(&self) -> usize;
     /// Commit structure to transcript
-    fn commit_to_transcript(&self, transcript: &mut transcript::Transcript<F, E>)
-    where
-        F: PrimeField32;
+    fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    );
 }
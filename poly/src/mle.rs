@@ -1,29 +1,210 @@
-use std::ops::{Add, Index, Mul, Sub};
+use std::ops::{Add, Mul, Sub};
 
 use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
 
 use crate::{Fields, MultilinearExtension};
 
+/// How the variables of a `MultilinearPoly` map to the bits of an evaluation index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VariableOrder {
+    /// The first variable is the most significant bit
+    #[default]
+    MsbFirst,
+    /// The first variable is the least significant bit
+    LsbFirst,
+}
+
+/// Evaluation table of a `MultilinearPoly`, tagged once for the whole table rather than
+/// per element so that base field data takes base field space
+#[derive(Debug, Clone)]
+pub enum MleEvaluations<F: Field, E: ExtensionField<F>> {
+    Base(Vec<F>),
+    Extension(Vec<E>),
+}
+
+impl<F: Field, E: ExtensionField<F>> MleEvaluations<F, E> {
+    /// Stores the table in the base field when every element is a base element
+    pub fn from_fields(evaluations: Vec<Fields<F, E>>) -> Self {
+        let base = evaluations
+            .iter()
+            .map(|eval| match eval {
+                Fields::Base(val) => Some(*val),
+                Fields::Extension(_) => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        match base {
+            Some(base) => Self::Base(base),
+            None => Self::Extension(
+                evaluations
+                    .iter()
+                    .map(|eval| eval.to_extension_field())
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Base(table) => table.len(),
+            Self::Extension(table) => table.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_base(&self) -> bool {
+        matches!(self, Self::Base(_))
+    }
+
+    pub fn get(&self, index: usize) -> Fields<F, E> {
+        match self {
+            Self::Base(table) => Fields::Base(table[index]),
+            Self::Extension(table) => Fields::Extension(table[index]),
+        }
+    }
+
+    /// Overwrites an entry, moving a base table to the extension field if `value` needs it
+    pub fn set(&mut self, index: usize, value: Fields<F, E>) {
+        match (&mut *self, value) {
+            (Self::Base(table), Fields::Base(value)) => table[index] = value,
+            (Self::Extension(table), value) => table[index] = value.to_extension_field(),
+            (Self::Base(_), Fields::Extension(value)) => {
+                *self = Self::Extension(self.to_extension());
+                self.set(index, Fields::Extension(value));
+            }
+        }
+    }
+
+    pub fn to_fields(&self) -> Vec<Fields<F, E>> {
+        (0..self.len()).map(|index| self.get(index)).collect()
+    }
+
+    pub fn to_extension(&self) -> Vec<E> {
+        match self {
+            Self::Base(table) => table.iter().map(|val| E::from_base(*val)).collect(),
+            Self::Extension(table) => table.clone(),
+        }
+    }
+
+    /// Sum of all entries, in the storage field
+    pub fn sum(&self) -> E {
+        #[cfg(feature = "parallel")]
+        if self.len() >= crate::PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            return match self {
+                Self::Base(table) => E::from_base(table.par_iter().copied().sum()),
+                Self::Extension(table) => table.par_iter().copied().sum(),
+            };
+        }
+
+        match self {
+            Self::Base(table) => E::from_base(table.iter().copied().sum()),
+            Self::Extension(table) => table.iter().copied().sum(),
+        }
+    }
+
+    /// Folds the entry pairs differing in the most significant index bit, or in the
+    /// least significant one, into the line through them at `point`.
+    /// An extension table is folded in place, a base table moves to the extension field.
+    fn fold(&mut self, most_significant: bool, point: &Fields<F, E>) {
+        let half = self.len() / 2;
+        let point = point.to_extension_field();
+        let pair = |j: usize| match most_significant {
+            true => (j, j + half),
+            false => (2 * j, 2 * j + 1),
+        };
+
+        match self {
+            Self::Base(table) => {
+                let fold = |j: usize| {
+                    let (lo, hi) = pair(j);
+                    point * (table[hi] - table[lo]) + table[lo]
+                };
+
+                #[cfg(feature = "parallel")]
+                if half >= crate::PARALLEL_THRESHOLD {
+                    use rayon::prelude::*;
+                    *self = Self::Extension((0..half).into_par_iter().map(fold).collect());
+                    return;
+                }
+
+                *self = Self::Extension((0..half).map(fold).collect());
+            }
+            Self::Extension(table) if most_significant => {
+                let (lower, upper) = table.split_at_mut(half);
+                let fold = |lo: &mut E, hi: &E| *lo += point * (*hi - *lo);
+
+                #[cfg(feature = "parallel")]
+                if half >= crate::PARALLEL_THRESHOLD {
+                    use rayon::prelude::*;
+                    lower
+                        .par_iter_mut()
+                        .zip(upper.par_iter())
+                        .for_each(|(lo, hi)| fold(lo, hi));
+                    table.truncate(half);
+                    return;
+                }
+
+                lower
+                    .iter_mut()
+                    .zip(upper.iter())
+                    .for_each(|(lo, hi)| fold(lo, hi));
+                table.truncate(half);
+            }
+            Self::Extension(table) => {
+                // entry j only reads entries 2j and 2j + 1, which are not overwritten yet
+                for j in 0..half {
+                    let (lo, hi) = (table[2 * j], table[2 * j + 1]);
+                    table[j] = lo + point * (hi - lo);
+                }
+                table.truncate(half);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct MultilinearPoly<F: Field, E: ExtensionField<F>> {
     /// The evaluations of the boolean hypercube {0,1}^n_vars
-    pub evaluations: Vec<Fields<F, E>>,
+    evaluations: MleEvaluations<F, E>,
     /// Number of variables
     n_vars: usize,
+    /// Variable to index bit mapping
+    order: VariableOrder,
 }
 
 impl<F: Field, E: ExtensionField<F>> MultilinearPoly<F, E> {
-    /// Instantiates a `MultilinearPoly` from a vec of field elements
+    /// Instantiates a `MultilinearPoly` from a vec of field elements, the table is kept
+    /// in the base field when every element is a base element
     pub fn new_from_vec(n_vars: usize, evaluations: Vec<Fields<F, E>>) -> Self {
+        Self::new(n_vars, MleEvaluations::from_fields(evaluations))
+    }
+
+    /// Instantiates a `MultilinearPoly` over a typed evaluation table
+    pub fn new(n_vars: usize, evaluations: MleEvaluations<F, E>) -> Self {
         // assert that the number of variables matches the number of evaluations
         assert_ne!(evaluations.len(), 0);
         assert_eq!(1 << n_vars, evaluations.len());
         Self {
             evaluations,
             n_vars,
+            order: VariableOrder::MsbFirst,
         }
     }
 
+    /// Instantiates a `MultilinearPoly` from base field evaluations
+    pub fn from_base(n_vars: usize, evaluations: Vec<F>) -> Self {
+        Self::new(n_vars, MleEvaluations::Base(evaluations))
+    }
+
+    /// Instantiates a `MultilinearPoly` from extension field evaluations
+    pub fn from_extension(n_vars: usize, evaluations: Vec<E>) -> Self {
+        Self::new(n_vars, MleEvaluations::Extension(evaluations))
+    }
+
     /// Instantiates a new `MultilinearPoly` pads with some given element until a power of two size
     pub fn new_extend_to_power_of_two(
         mut evaluations: Vec<Fields<F, E>>,
@@ -36,15 +217,57 @@ impl<F: Field, E: ExtensionField<F>> MultilinearPoly<F, E> {
             evaluations.len().next_power_of_two()
         };
         evaluations.resize(target_size, pad_element);
-        Self {
-            evaluations,
-            n_vars: target_size.ilog2() as usize,
-        }
+        Self::new_from_vec(target_size.ilog2() as usize, evaluations)
     }
 
     /// Creates a Zero Multilinear poly
     pub fn zero(num_vars: usize) -> Self {
-        Self::new_from_vec(num_vars, vec![Fields::Base(F::zero()); 1 << num_vars])
+        Self::from_base(num_vars, vec![F::zero(); 1 << num_vars])
+    }
+
+    /// Reads the same evaluation table with the given variable order
+    pub fn with_order(mut self, order: VariableOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn order(&self) -> VariableOrder {
+        self.order
+    }
+
+    /// The evaluation table
+    pub fn evaluations(&self) -> &MleEvaluations<F, E> {
+        &self.evaluations
+    }
+
+    /// Evaluation at the hypercube point with the given index
+    pub fn get(&self, index: usize) -> Fields<F, E> {
+        self.evaluations.get(index)
+    }
+
+    /// Overwrites the evaluation at the hypercube point with the given index
+    pub fn set(&mut self, index: usize, value: Fields<F, E>) {
+        self.evaluations.set(index, value);
+    }
+
+    /// Fixes the last variable to `point`, halving the table in place once it is in
+    /// the extension field
+    pub fn fix_last_var_in_place(&mut self, point: &Fields<F, E>) {
+        self.fold_var(self.order == VariableOrder::LsbFirst, point);
+    }
+
+    /// Evaluations at (0, x) and (1, x), x being the i'th point of the remaining variables
+    pub fn first_var_pair(&self, i: usize) -> (Fields<F, E>, Fields<F, E>) {
+        match self.order {
+            VariableOrder::MsbFirst => (self.get(i), self.get(i + self.evaluations.len() / 2)),
+            VariableOrder::LsbFirst => (self.get(2 * i), self.get(2 * i + 1)),
+        }
+    }
+
+    fn fold_var(&mut self, most_significant: bool, point: &Fields<F, E>) {
+        assert!(self.n_vars > 0, "no variable left to fix");
+        self.evaluations.fold(most_significant, point);
+        self.n_vars -= 1;
     }
 }
 
@@ -56,35 +279,48 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for MultilinearP
         // ensure we don't have more points than variables
         assert!(points.len() <= self.n_vars);
 
-        let mut new_evaluations = self.evaluations.clone();
-
-        // for each partial point, fold the evaluations in half
-        let mut mid_point = new_evaluations.len() / 2;
+        let mut poly = self.clone();
         for point in points {
-            for i in 0..mid_point {
-                let left = new_evaluations[i].to_extension_field();
-                let right = new_evaluations[i + mid_point].to_extension_field();
-                new_evaluations[i] = match point {
-                    // if the evaluation point is in the boolean hypercube
-                    // return result from table directly
-                    a if a.to_extension_field().is_zero() => Fields::Extension(left),
-                    a if a.to_extension_field().is_one() => Fields::Extension(right),
-
-                    // linear interpolation
-                    // (1-r) * left + r * right
-                    // left - r.left + r.right
-                    // left - r (left - right)
-                    _ => Fields::Extension(left - point.to_extension_field() * (left - right)),
-                }
-            }
-            mid_point /= 2;
+            poly.fix_first_var_in_place(point);
         }
+        poly
+    }
+
+    /// Fixes the first variable to `point`, a base table moves to the extension field
+    /// on its first fold and is folded in place from then on
+    fn fix_first_var_in_place(&mut self, point: &Fields<F, E>) {
+        self.fold_var(self.order == VariableOrder::MsbFirst, point);
+    }
+
+    /// Collapses the first log2(weights.len()) variables into a weighted
+    /// sum of the sub-tables they index, `h` indexing the first variables in the
+    /// poly's own variable order
+    fn fold_first_vars(&self, weights: &[Fields<F, E>]) -> Self {
+        assert!(weights.len().is_power_of_two());
+        let k = weights.len().ilog2() as usize;
+        assert!(k <= self.n_vars);
+
+        let n_vars = self.n_vars - k;
+        let size = 1 << n_vars;
+        let index = |h: usize, i: usize| match self.order {
+            VariableOrder::MsbFirst => h * size + i,
+            VariableOrder::LsbFirst => (i << k) | h,
+        };
+        let evaluations = (0..size)
+            .map(|i| {
+                weights
+                    .iter()
+                    .enumerate()
+                    .fold(E::zero(), |acc, (h, weight)| {
+                        acc + (*weight * self.get(index(h, i))).to_extension_field()
+                    })
+            })
+            .collect();
 
-        // truncate and return new polynomial
-        let n_vars = self.n_vars - points.len();
         Self {
-            evaluations: new_evaluations[..(1 << n_vars)].to_vec(),
+            evaluations: MleEvaluations::Extension(evaluations),
             n_vars,
+            order: self.order,
         }
     }
 
@@ -93,7 +329,7 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for MultilinearP
     fn evaluate(&self, points: &[Fields<F, E>]) -> Fields<F, E> {
         // ensure number of points exactly matches number of variables
         assert_eq!(self.n_vars, points.len());
-        self.partial_evaluate(points).evaluations[0]
+        self.partial_evaluate(points).get(0)
     }
 
     /// Polynomial max variable degree
@@ -103,11 +339,7 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for MultilinearP
 
     /// Returns the sum of evaluations overr the boolean hypercube
     fn sum_over_hypercube(&self) -> Fields<F, E> {
-        self.evaluations
-            .iter()
-            .fold(Fields::Base(F::zero()), |acc, curr| {
-                Fields::Extension(acc.to_extension_field() + curr.to_extension_field())
-            })
+        Fields::Extension(self.evaluations.sum())
     }
 
     /// Number of variables in the `MultilinearPoly`
@@ -116,32 +348,61 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for MultilinearP
     }
 
     /// Commit `MultilinearPoly` to transcript
-    fn commit_to_transcript(&self, transcript: &mut transcript::Transcript<F, E>)
-    where
-        F: p3_field::PrimeField32,
-    {
-        transcript.observe(&self.evaluations);
+    fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.absorb_into(transcript);
+    }
+}
+
+/// Tables are equal when their values are, whichever field they are stored in
+impl<F: Field, E: ExtensionField<F>> PartialEq for MleEvaluations<F, E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Base(lhs), Self::Base(rhs)) => lhs == rhs,
+            (Self::Extension(lhs), Self::Extension(rhs)) => lhs == rhs,
+            (Self::Base(base), Self::Extension(ext)) | (Self::Extension(ext), Self::Base(base)) => {
+                base.len() == ext.len()
+                    && base
+                        .iter()
+                        .zip(ext)
+                        .all(|(lhs, rhs)| E::from_base(*lhs) == *rhs)
+            }
+        }
     }
 }
 
-impl<F: Field, E: ExtensionField<F>> Index<usize> for MultilinearPoly<F, E> {
-    type Output = Fields<F, E>;
+/// Absorbed straight from the typed table, with the same encoding as the `Fields` sequence
+/// of its values so the commitment does not depend on the storage
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for MleEvaluations<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.len().absorb_into(transcript);
+        match self {
+            Self::Base(table) => {
+                for val in table {
+                    transcript.observe_ext_element(&[E::from_base(*val)]);
+                }
+            }
+            Self::Extension(table) => transcript.observe_ext_element(table),
+        }
+    }
+}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.evaluations[index]
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for MultilinearPoly<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.num_vars().absorb_into(transcript);
+        self.evaluations.absorb_into(transcript);
     }
 }
 
 impl<F: Field, E: ExtensionField<F>> From<Vec<E>> for MultilinearPoly<F, E> {
     fn from(evaluations: Vec<E>) -> Self {
         let n_vars = (evaluations.len() as f64).log2() as usize;
-        let evaluations = evaluations
-            .into_iter()
-            .map(|eval| Fields::Extension(eval))
-            .collect();
         MultilinearPoly {
             n_vars,
-            evaluations,
+            evaluations: MleEvaluations::Extension(evaluations),
+            order: VariableOrder::MsbFirst,
         }
     }
 }
@@ -149,14 +410,10 @@ impl<F: Field, E: ExtensionField<F>> From<Vec<E>> for MultilinearPoly<F, E> {
 impl<F: Field, E: ExtensionField<F>> From<&Vec<F>> for MultilinearPoly<F, E> {
     fn from(evaluations: &Vec<F>) -> Self {
         let n_vars = (evaluations.len() as f64).log2() as usize;
-        let evaluations = evaluations
-            .iter()
-            .map(|&x| Fields::<F, E>::Base(x))
-            .collect::<Vec<_>>();
-
         MultilinearPoly {
             n_vars,
-            evaluations,
+            evaluations: MleEvaluations::Base(evaluations.clone()),
+            order: VariableOrder::MsbFirst,
         }
     }
 }
@@ -167,7 +424,8 @@ impl<F: Field, E: ExtensionField<F>> From<Vec<Fields<F, E>>> for MultilinearPoly
 
         MultilinearPoly {
             n_vars,
-            evaluations,
+            evaluations: MleEvaluations::from_fields(evaluations),
+            order: VariableOrder::MsbFirst,
         }
     }
 }
@@ -179,14 +437,29 @@ impl<F: Field, E: ExtensionField<F>> Add for MultilinearPoly<F, E> {
         if self.n_vars != other.n_vars {
             panic!("Polynomials must have the same number of variables");
         }
+        assert_eq!(
+            self.order, other.order,
+            "Polynomials must share a variable order"
+        );
 
-        let mut new_evaluations = Vec::new();
+        let evaluations = match (self.evaluations, other.evaluations) {
+            (MleEvaluations::Base(lhs), MleEvaluations::Base(rhs)) => {
+                MleEvaluations::Base(lhs.into_iter().zip(rhs).map(|(l, r)| l + r).collect())
+            }
+            (lhs, rhs) => MleEvaluations::Extension(
+                lhs.to_extension()
+                    .into_iter()
+                    .zip(rhs.to_extension())
+                    .map(|(l, r)| l + r)
+                    .collect(),
+            ),
+        };
 
-        for i in 0..self.evaluations.len() {
-            new_evaluations.push(self.evaluations[i] + other.evaluations[i]);
+        MultilinearPoly {
+            evaluations,
+            n_vars: self.n_vars,
+            order: self.order,
         }
-
-        MultilinearPoly::from(new_evaluations)
     }
 }
 
@@ -197,14 +470,29 @@ impl<F: Field, E: ExtensionField<F>> Sub for MultilinearPoly<F, E> {
         if self.n_vars != other.n_vars {
             panic!("Polynomials must have the same number of variables");
         }
+        assert_eq!(
+            self.order, other.order,
+            "Polynomials must share a variable order"
+        );
 
-        let mut new_evaluations = Vec::new();
+        let evaluations = match (self.evaluations, other.evaluations) {
+            (MleEvaluations::Base(lhs), MleEvaluations::Base(rhs)) => {
+                MleEvaluations::Base(lhs.into_iter().zip(rhs).map(|(l, r)| l - r).collect())
+            }
+            (lhs, rhs) => MleEvaluations::Extension(
+                lhs.to_extension()
+                    .into_iter()
+                    .zip(rhs.to_extension())
+                    .map(|(l, r)| l - r)
+                    .collect(),
+            ),
+        };
 
-        for i in 0..self.evaluations.len() {
-            new_evaluations.push(self.evaluations[i] - other.evaluations[i]);
+        MultilinearPoly {
+            evaluations,
+            n_vars: self.n_vars,
+            order: self.order,
         }
-
-        MultilinearPoly::from(new_evaluations)
     }
 }
 
@@ -212,22 +500,34 @@ impl<F: Field, E: ExtensionField<F>> Mul<Fields<F, E>> for MultilinearPoly<F, E>
     type Output = Self;
 
     fn mul(self, other: Fields<F, E>) -> Self::Output {
-        let evaluations = self
-            .evaluations
-            .iter()
-            .map(|eval| *eval * other)
-            .collect::<Vec<_>>();
+        let evaluations = match (self.evaluations, other) {
+            (MleEvaluations::Base(table), Fields::Base(scalar)) => {
+                MleEvaluations::Base(table.into_iter().map(|eval| eval * scalar).collect())
+            }
+            (table, scalar) => MleEvaluations::Extension(
+                table
+                    .to_extension()
+                    .into_iter()
+                    .map(|eval| eval * scalar.to_extension_field())
+                    .collect(),
+            ),
+        };
 
-        MultilinearPoly::from(evaluations)
+        MultilinearPoly {
+            evaluations,
+            n_vars: self.n_vars,
+            order: self.order,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MultilinearPoly;
-    use crate::{MultilinearExtension, mle::Fields};
+    use super::{MleEvaluations, MultilinearPoly, VariableOrder};
+    use crate::{MultilinearExtension, mle::Fields, utils::generate_eq};
     use p3_field::{AbstractField, extension::BinomialExtensionField};
     use p3_goldilocks::Goldilocks as F;
+    use transcript::{KeccakChallenger64, Transcript, absorb::TranscriptAbsorb};
 
     type E = BinomialExtensionField<F, 2>;
 
@@ -272,9 +572,9 @@ mod tests {
             Fields::Base(F::zero()),
         );
         assert_eq!(mle.num_vars(), 3);
-        assert_eq!(mle.evaluations.len(), 8);
-        assert_eq!(mle.evaluations[6], Fields::Base(F::from_canonical_u64(0)));
-        assert_eq!(mle.evaluations[7], Fields::Base(F::from_canonical_u64(0)));
+        assert_eq!(mle.evaluations().len(), 8);
+        assert_eq!(mle.get(6), Fields::Base(F::from_canonical_u64(0)));
+        assert_eq!(mle.get(7), Fields::Base(F::from_canonical_u64(0)));
     }
 
     #[test]
@@ -314,13 +614,122 @@ mod tests {
             Fields::Base(F::from_canonical_u64(2)),
             Fields::Base(F::from_canonical_u64(3)),
         ]);
-        assert_eq!(f_a.evaluations.len(), 2);
+        assert_eq!(f_a.evaluations().len(), 2);
+        assert_eq!(
+            f_a.evaluations(),
+            &MleEvaluations::Extension(vec![E::from_canonical_u64(12), E::from_canonical_u64(21)])
+        );
+    }
+
+    #[test]
+    fn test_fold_first_vars_with_eq_weights() {
+        let poly = f_abc();
+        let point = to_fields(vec![2, 3]);
+        assert_eq!(
+            poly.fold_first_vars(&generate_eq(&point)),
+            poly.partial_evaluate(&point)
+        );
+    }
+
+    #[test]
+    fn test_fix_vars_in_place() {
+        let point = to_fields(vec![2, 3, 4]);
+
+        let mut poly = f_abc();
+        poly.fix_first_var_in_place(&point[0]);
+        assert_eq!(poly, f_abc().partial_evaluate(&point[..1]));
+
+        // fixing the last variable leaves the first ones free
+        let mut poly = f_abc();
+        poly.fix_last_var_in_place(&point[2]);
+        assert_eq!(poly.num_vars(), 2);
+        assert_eq!(poly.evaluate(&point[..2]), f_abc().evaluate(&point));
+    }
+
+    #[test]
+    fn test_lsb_first_order() {
+        let point = to_fields(vec![2, 3, 4]);
+        let reversed = point.iter().rev().copied().collect::<Vec<_>>();
+        let lsb = f_abc().with_order(VariableOrder::LsbFirst);
+
+        // the first variable of an LSB-first poly is the last one of the MSB-first reading
+        assert_eq!(lsb.evaluate(&point), f_abc().evaluate(&reversed));
+        assert_eq!(
+            lsb.partial_evaluate(&point[..1]).evaluate(&point[1..]),
+            lsb.evaluate(&point)
+        );
+        assert_eq!(
+            lsb.fold_first_vars(&generate_eq(&[point[1], point[0]])),
+            lsb.partial_evaluate(&point[..2])
+        );
+
+        let mut fixed = lsb.clone();
+        fixed.fix_last_var_in_place(&point[2]);
+        assert_eq!(fixed.evaluate(&point[..2]), lsb.evaluate(&point));
+        assert_eq!(lsb.first_var_pair(1), (lsb.get(2), lsb.get(3)));
+    }
+
+    #[test]
+    fn test_base_storage() {
+        let mut poly = f_abc();
+        assert!(poly.evaluations().is_base());
+        assert!((poly.clone() + f_abc()).evaluations().is_base());
+
+        // mixed input is stored in the extension field
+        let mut mixed = to_fields(vec![1, 2, 3, 4]);
+        mixed[1] = Fields::Extension(E::from_canonical_u64(2));
+        let mixed = MultilinearPoly::new_from_vec(2, mixed);
+        assert!(!mixed.evaluations().is_base());
+        assert_eq!(
+            mixed.evaluations(),
+            &MleEvaluations::Extension((1..=4).map(E::from_canonical_u64).collect())
+        );
+
+        // the first fold leaves the base field, the evaluation is unchanged
+        let point = to_fields(vec![2, 3, 4]);
+        let expected = poly.evaluate(&point);
+        poly.fix_first_var_in_place(&point[0]);
+        assert!(!poly.evaluations().is_base());
+        assert_eq!(poly.evaluate(&point[1..]), expected);
+
+        let mut zero = MultilinearPoly::<F, E>::zero(1);
+        zero.set(1, Fields::Extension(E::one()));
+        assert_eq!(zero.get(1), Fields::Extension(E::one()));
+        assert!(!zero.evaluations().is_base());
+    }
+
+    #[test]
+    fn test_storage_independent_equality_and_absorb() {
+        let base =
+            MultilinearPoly::<F, E>::from_base(2, (1..=4).map(F::from_canonical_u64).collect());
+        let extension = MultilinearPoly::<F, E>::from_extension(
+            2,
+            (1..=4).map(E::from_canonical_u64).collect(),
+        );
+        assert_eq!(base, extension);
+        assert_eq!(extension, base);
+        assert_ne!(
+            base,
+            MultilinearPoly::from_extension(2, (2..=5).map(E::from_canonical_u64).collect())
+        );
+
+        type T = Transcript<F, E, KeccakChallenger64<F>>;
+        let challenge = |value: &dyn Fn(&mut T)| {
+            let mut transcript = T::new();
+            value(&mut transcript);
+            transcript.challenge(b"challenge")
+        };
+        let expected = challenge(&|transcript| {
+            2_usize.absorb_into(transcript);
+            base.evaluations().to_fields().absorb_into(transcript);
+        });
         assert_eq!(
-            f_a.evaluations,
-            &[
-                Fields::Extension(E::from_canonical_u64(12)),
-                Fields::Extension(E::from_canonical_u64(21))
-            ]
+            challenge(&|transcript| base.absorb_into(transcript)),
+            expected
+        );
+        assert_eq!(
+            challenge(&|transcript| extension.absorb_into(transcript)),
+            expected
         );
     }
 
@@ -335,6 +744,32 @@ mod tests {
         assert_eq!(evaluation, Fields::Extension(E::from_canonical_u64(48)));
     }
 
+    #[test]
+    fn test_evaluation_above_parallel_threshold() {
+        // large enough to take the threaded path when the `parallel` feature is enabled
+        let n_vars = 14;
+        let poly = MultilinearPoly::new_from_vec(
+            n_vars,
+            to_fields((0..1 << n_vars).map(|i| i * 7 + 3).collect()),
+        );
+        let point = to_fields((0..n_vars as u64).map(|i| i + 2).collect());
+
+        let expected = generate_eq(&point)
+            .into_iter()
+            .zip(poly.evaluations().to_fields())
+            .fold(E::zero(), |acc, (eq, eval)| {
+                acc + (eq * eval).to_extension_field()
+            });
+
+        assert_eq!(poly.evaluate(&point), Fields::Extension(expected));
+        assert_eq!(
+            poly.sum_over_hypercube(),
+            Fields::Extension(E::from_canonical_u64(
+                (0..1u64 << n_vars).map(|i| i * 7 + 3).sum()
+            ))
+        );
+    }
+
     #[test]
     fn test_sum_over_boolean_hypercube() {
         let poly = f_abc();
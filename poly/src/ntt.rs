@@ -0,0 +1,194 @@
+//! Radix-2 number theoretic transform over two-adic fields.
+//! Coefficients are lowest degree first and evaluations are over the subgroup <ω> of
+//! order n in natural order, ω^0, ω^1, .., ω^(n-1), with ω = F::two_adic_generator(log n).
+use p3_field::{ExtensionField, TwoAdicField};
+
+/// Coefficients to evaluations over <ω>, in place
+pub fn ntt<F: TwoAdicField, E: ExtensionField<F>>(values: &mut [E]) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "ntt size must be a power of two");
+    let log_n = n.ilog2() as usize;
+    assert!(log_n <= F::TWO_ADICITY, "ntt size exceeds the two-adicity");
+
+    bit_reverse(values);
+    for log_len in 1..=log_n {
+        let half = 1 << (log_len - 1);
+        let twiddles = F::two_adic_generator(log_len)
+            .powers()
+            .take(half)
+            .collect::<Vec<_>>();
+        for chunk in values.chunks_mut(1 << log_len) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for ((lo, hi), twiddle) in lo.iter_mut().zip(hi).zip(&twiddles) {
+                let (u, v) = (*lo, *hi * *twiddle);
+                *lo = u + v;
+                *hi = u - v;
+            }
+        }
+    }
+}
+
+/// Evaluations over <ω> to coefficients, in place
+pub fn intt<F: TwoAdicField, E: ExtensionField<F>>(values: &mut [E]) {
+    // evaluating at ω^(-k) = ω^(n-k) inverts the transform up to a factor n
+    ntt::<F, E>(values);
+    values[1..].reverse();
+    let n_inv = F::from_canonical_usize(values.len()).inverse();
+    for value in values.iter_mut() {
+        *value *= n_inv;
+    }
+}
+
+/// Coefficients to evaluations over the coset shift · <ω>, in place
+pub fn coset_ntt<F: TwoAdicField, E: ExtensionField<F>>(values: &mut [E], shift: F) {
+    for (value, power) in values.iter_mut().zip(shift.powers()) {
+        *value *= power;
+    }
+    ntt::<F, E>(values);
+}
+
+/// Evaluations over the coset shift · <ω> to coefficients, in place
+pub fn coset_intt<F: TwoAdicField, E: ExtensionField<F>>(values: &mut [E], shift: F) {
+    intt::<F, E>(values);
+    for (value, power) in values.iter_mut().zip(shift.inverse().powers()) {
+        *value *= power;
+    }
+}
+
+/// Low degree extension of evaluations over <ω> of order n to the coset shift · <ω'>
+/// of order n · 2^log_blowup
+pub fn coset_lde<F: TwoAdicField, E: ExtensionField<F>>(
+    evaluations: &[E],
+    shift: F,
+    log_blowup: usize,
+) -> Vec<E> {
+    let mut coefficients = evaluations.to_vec();
+    intt::<F, E>(&mut coefficients);
+    coefficients.resize(evaluations.len() << log_blowup, E::zero());
+    coset_ntt::<F, E>(&mut coefficients, shift);
+    coefficients
+}
+
+/// Product of two polynomials in coefficient form, lowest degree first
+pub fn multiply<F: TwoAdicField, E: ExtensionField<F>>(lhs: &[E], rhs: &[E]) -> Vec<E> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+    let product_len = lhs.len() + rhs.len() - 1;
+    let n = product_len.next_power_of_two();
+
+    let (mut lhs, mut rhs) = (lhs.to_vec(), rhs.to_vec());
+    lhs.resize(n, E::zero());
+    rhs.resize(n, E::zero());
+    ntt::<F, E>(&mut lhs);
+    ntt::<F, E>(&mut rhs);
+    for (l, r) in lhs.iter_mut().zip(rhs) {
+        *l *= r;
+    }
+    intt::<F, E>(&mut lhs);
+    lhs.truncate(product_len);
+    lhs
+}
+
+fn bit_reverse<T>(values: &mut [T]) {
+    let n = values.len();
+    if n <= 2 {
+        return;
+    }
+    let shift = usize::BITS - n.ilog2();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_field::{
+        AbstractField, ExtensionField, TwoAdicField, extension::BinomialExtensionField,
+    };
+    use p3_goldilocks::Goldilocks;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::{coset_intt, coset_lde, intt, multiply, ntt};
+
+    fn random_values<E: AbstractField>(rng: &mut StdRng, len: usize) -> Vec<E> {
+        (0..len)
+            .map(|_| E::from_canonical_u32(rng.gen_range(0..1 << 30)))
+            .collect()
+    }
+
+    fn horner<F: TwoAdicField, E: ExtensionField<F>>(coefficients: &[E], point: F) -> E {
+        coefficients
+            .iter()
+            .rev()
+            .fold(E::zero(), |acc, coeff| acc * point + *coeff)
+    }
+
+    fn check_against_naive_evaluation<F: TwoAdicField, E: ExtensionField<F>>() {
+        let mut rng = StdRng::seed_from_u64(47);
+        for log_n in 0..7 {
+            let coefficients = random_values::<E>(&mut rng, 1 << log_n);
+            let mut evaluations = coefficients.clone();
+            ntt::<F, E>(&mut evaluations);
+
+            let naive = F::two_adic_generator(log_n)
+                .powers()
+                .take(1 << log_n)
+                .map(|point| horner(&coefficients, point))
+                .collect::<Vec<_>>();
+            assert_eq!(evaluations, naive);
+
+            intt::<F, E>(&mut evaluations);
+            assert_eq!(evaluations, coefficients);
+        }
+
+        // the coset extension agrees with the polynomial on the larger coset
+        let coefficients = random_values::<E>(&mut rng, 8);
+        let mut evaluations = coefficients.clone();
+        ntt::<F, E>(&mut evaluations);
+        let shift = F::generator();
+        let extended = coset_lde::<F, E>(&evaluations, shift, 2);
+        let naive = F::two_adic_generator(5)
+            .powers()
+            .take(32)
+            .map(|power| horner(&coefficients, shift * power))
+            .collect::<Vec<_>>();
+        assert_eq!(extended, naive);
+
+        let mut recovered = extended;
+        coset_intt::<F, E>(&mut recovered, shift);
+        assert_eq!(recovered[..8], coefficients);
+        assert!(recovered[8..].iter().all(|coeff| coeff.is_zero()));
+    }
+
+    #[test]
+    fn test_ntt_goldilocks() {
+        check_against_naive_evaluation::<Goldilocks, Goldilocks>();
+        check_against_naive_evaluation::<Goldilocks, BinomialExtensionField<Goldilocks, 2>>();
+    }
+
+    #[test]
+    fn test_ntt_baby_bear() {
+        check_against_naive_evaluation::<BabyBear, BabyBear>();
+        check_against_naive_evaluation::<BabyBear, BinomialExtensionField<BabyBear, 4>>();
+    }
+
+    #[test]
+    fn test_multiply() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let lhs = random_values::<Goldilocks>(&mut rng, 5);
+        let rhs = random_values::<Goldilocks>(&mut rng, 12);
+
+        let mut naive = vec![Goldilocks::zero(); 16];
+        for (i, l) in lhs.iter().enumerate() {
+            for (j, r) in rhs.iter().enumerate() {
+                naive[i + j] += *l * *r;
+            }
+        }
+        assert_eq!(multiply::<Goldilocks, Goldilocks>(&lhs, &rhs), naive);
+    }
+}
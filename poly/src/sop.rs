@@ -0,0 +1,295 @@
+//! This module contains the implementation of the sum-of-products polynomial.
+//! A sum-of-products polynomial has the form Σ_j c_j · Π_k f_{j,k}(x), where the
+//! MLEs f_{j,k} are deduplicated and shared between terms.
+use std::hash::{DefaultHasher, Hasher};
+
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
+use crate::mle::MultilinearPoly;
+use crate::{Fields, MultilinearExtension};
+
+/// A product term: coefficient and indices of the MLE factors
+pub type Term<F, E> = (Fields<F, E>, Vec<usize>);
+
+#[derive(Debug, Clone)]
+pub struct SumOfProducts<F: Field, E: ExtensionField<F>> {
+    /// Deduplicated MLEs referenced by the terms
+    mles: Vec<MultilinearPoly<F, E>>,
+    /// Table hashes of `mles` used for deduplication, cleared once the MLEs are folded
+    hashes: Vec<u64>,
+    /// Product terms over `mles`
+    terms: Vec<Term<F, E>>,
+    /// Number of variables in the polynomial
+    num_vars: usize,
+}
+
+impl<F: Field, E: ExtensionField<F>> SumOfProducts<F, E> {
+    /// Creates an empty sum-of-products polynomial over `num_vars` variables
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            mles: vec![],
+            hashes: vec![],
+            terms: vec![],
+            num_vars,
+        }
+    }
+
+    /// Adds an MLE to the polynomial, returning its index.
+    /// An MLE that is already present is reused rather than stored twice, tables are
+    /// only compared when their hashes match.
+    pub fn add_mle(&mut self, mle: MultilinearPoly<F, E>) -> usize {
+        assert_eq!(
+            mle.num_vars(),
+            self.num_vars,
+            "MLEs must have the same number of variables"
+        );
+        if self.hashes.len() != self.mles.len() {
+            self.hashes = self.mles.iter().map(table_hash).collect();
+        }
+
+        let hash = table_hash(&mle);
+        let existing = self
+            .hashes
+            .iter()
+            .zip(&self.mles)
+            .position(|(existing_hash, existing)| *existing_hash == hash && *existing == mle);
+        match existing {
+            Some(index) => index,
+            None => {
+                self.mles.push(mle);
+                self.hashes.push(hash);
+                self.mles.len() - 1
+            }
+        }
+    }
+
+    /// Adds the term `coeff · Π mles`, deduplicating the MLEs
+    pub fn add_term(&mut self, coeff: Fields<F, E>, mles: Vec<MultilinearPoly<F, E>>) {
+        let indices = mles.into_iter().map(|mle| self.add_mle(mle)).collect();
+        self.terms.push((coeff, indices));
+    }
+
+    /// Adds the term `coeff · Π mles[i]` over MLEs already in the polynomial
+    pub fn add_term_by_index(&mut self, coeff: Fields<F, E>, indices: Vec<usize>) {
+        assert!(
+            indices.iter().all(|index| *index < self.mles.len()),
+            "term references an unknown MLE"
+        );
+        self.terms.push((coeff, indices));
+    }
+
+    /// Deduplicated MLEs in the polynomial
+    pub fn mles(&self) -> &[MultilinearPoly<F, E>] {
+        &self.mles
+    }
+
+    /// Product terms of the polynomial
+    pub fn terms(&self) -> &[Term<F, E>] {
+        &self.terms
+    }
+
+    /// Combines per-MLE values according to the terms
+    pub fn combine(&self, values: &[Fields<F, E>]) -> Fields<F, E> {
+        self.terms
+            .iter()
+            .fold(Fields::Base(F::zero()), |acc, (coeff, indices)| {
+                acc + indices
+                    .iter()
+                    .fold(*coeff, |product, index| product * values[*index])
+            })
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> PartialEq for SumOfProducts<F, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mles == other.mles && self.terms == other.terms && self.num_vars == other.num_vars
+    }
+}
+
+/// Hash of the evaluation values of an MLE, independent of the field they are stored in
+fn table_hash<F: Field, E: ExtensionField<F>>(mle: &MultilinearPoly<F, E>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for i in 0..1 << mle.num_vars() {
+        mle.get(i).to_extension_field().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for SumOfProducts<F, E> {
+    /// Evaluates every MLE once, then combines the terms
+    fn evaluate(&self, point: &[Fields<F, E>]) -> Fields<F, E> {
+        let values = self
+            .mles
+            .iter()
+            .map(|mle| mle.evaluate(point))
+            .collect::<Vec<_>>();
+        self.combine(&values)
+    }
+
+    /// Partially evaluates every MLE once, the terms are shared as-is
+    fn partial_evaluate(&self, point: &[Fields<F, E>]) -> Self {
+        Self {
+            mles: self
+                .mles
+                .iter()
+                .map(|mle| mle.partial_evaluate(point))
+                .collect(),
+            hashes: vec![],
+            terms: self.terms.clone(),
+            num_vars: self.num_vars - point.len(),
+        }
+    }
+
+    /// Fixes the first variable of every MLE in place, the terms are untouched
+    fn fix_first_var_in_place(&mut self, point: &Fields<F, E>) {
+        for mle in self.mles.iter_mut() {
+            mle.fix_first_var_in_place(point);
+        }
+        self.hashes.clear();
+        self.num_vars -= 1;
+    }
+
+    /// Folds the first variables of every MLE once with the given weights
+    fn fold_first_vars(&self, weights: &[Fields<F, E>]) -> Self {
+        Self {
+            mles: self
+                .mles
+                .iter()
+                .map(|mle| mle.fold_first_vars(weights))
+                .collect(),
+            hashes: vec![],
+            terms: self.terms.clone(),
+            num_vars: self.num_vars - weights.len().ilog2() as usize,
+        }
+    }
+
+    /// Max number of MLE factors in a term
+    fn max_degree(&self) -> usize {
+        self.terms
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the sum of evaluations over the boolean hypercube
+    fn sum_over_hypercube(&self) -> Fields<F, E> {
+        let mut sum = E::zero();
+        let mut row = vec![Fields::Base(F::zero()); self.mles.len()];
+        for i in 0..(1 << self.num_vars) {
+            for (value, mle) in row.iter_mut().zip(&self.mles) {
+                *value = mle.get(i);
+            }
+            sum += self.combine(&row).to_extension_field();
+        }
+        Fields::Extension(sum)
+    }
+
+    /// Number of variables in the polynomial
+    fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Commit the MLEs and the term structure to the transcript
+    fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.absorb_into(transcript);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for SumOfProducts<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.mles.absorb_into(transcript);
+        self.terms.absorb_into(transcript);
+        self.num_vars.absorb_into(transcript);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::product_poly;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_goldilocks::Goldilocks as F;
+
+    type E = BinomialExtensionField<F, 2>;
+
+    fn to_fields(vals: Vec<u64>) -> Vec<Fields<F, E>> {
+        vals.into_iter()
+            .map(|v| Fields::<F, E>::Base(F::from_canonical_u64(v)))
+            .collect()
+    }
+
+    fn f_abc() -> MultilinearPoly<F, E> {
+        // f(a, b, c) = 2ab + 3bc
+        MultilinearPoly::new_from_vec(3, to_fields(vec![0, 0, 0, 3, 0, 0, 2, 5]))
+    }
+
+    fn g_abc() -> MultilinearPoly<F, E> {
+        MultilinearPoly::new_from_vec(3, to_fields(vec![1, 2, 3, 4, 5, 6, 7, 8]))
+    }
+
+    // 3 · f · g + 2 · f · f
+    fn sop() -> SumOfProducts<F, E> {
+        let mut sop = SumOfProducts::new(3);
+        sop.add_term(
+            Fields::Base(F::from_canonical_u64(3)),
+            vec![f_abc(), g_abc()],
+        );
+        sop.add_term(
+            Fields::Base(F::from_canonical_u64(2)),
+            vec![f_abc(), f_abc()],
+        );
+        sop
+    }
+
+    #[test]
+    fn test_mles_are_deduplicated() {
+        let sop = sop();
+        assert_eq!(sop.mles().len(), 2);
+        assert_eq!(sop.terms()[1].1, vec![0, 0]);
+        assert_eq!(sop.max_degree(), 2);
+    }
+
+    #[test]
+    fn test_mles_are_deduplicated_after_folding() {
+        let mut sop = sop();
+        let point = Fields::Base(F::from_canonical_u64(4));
+        sop.fix_first_var_in_place(&point);
+
+        let mut folded_g = g_abc();
+        folded_g.fix_first_var_in_place(&point);
+        sop.add_term(Fields::Base(F::one()), vec![folded_g]);
+        assert_eq!(sop.mles().len(), 2);
+        assert_eq!(sop.terms()[2].1, vec![1]);
+    }
+
+    #[test]
+    fn test_evaluate_matches_products() {
+        let sop = sop();
+        let point = to_fields(vec![2, 3, 4]);
+        let f = f_abc().evaluate(&point);
+        let g = g_abc().evaluate(&point);
+        let expected = Fields::Base(F::from_canonical_u64(3)) * f * g
+            + Fields::Base(F::from_canonical_u64(2)) * f * f;
+        assert_eq!(sop.evaluate(&point), expected);
+    }
+
+    #[test]
+    fn test_sum_over_hypercube_and_partial_evaluate() {
+        let sop = sop();
+        let expected = product_poly(vec![f_abc(), g_abc()]).sum_over_hypercube()
+            * Fields::Base(F::from_canonical_u64(3))
+            + product_poly(vec![f_abc(), f_abc()]).sum_over_hypercube()
+                * Fields::Base(F::from_canonical_u64(2));
+        assert_eq!(sop.sum_over_hypercube(), expected);
+
+        let point = to_fields(vec![5, 7, 9]);
+        let partial = sop.partial_evaluate(&point[..1]);
+        assert_eq!(partial.num_vars(), 2);
+        assert_eq!(partial.evaluate(&point[1..]), sop.evaluate(&point));
+    }
+}
@@ -0,0 +1,284 @@
+//! Sparse multilinear polynomials, for wiring predicates and selectors whose hypercube
+//! evaluations are almost all zero.
+//! Only the non-zero evaluations are stored as (index, value) pairs, the index follows the
+//! dense layout so the first variable is the most significant bit.
+use std::collections::BTreeMap;
+
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
+use crate::{
+    Fields, MultilinearExtension,
+    mle::{MultilinearPoly, VariableOrder},
+    utils::generate_eq,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMultilinearPoly<F: Field, E: ExtensionField<F>> {
+    /// Non-zero evaluations over the boolean hypercube, sorted by index
+    entries: Vec<(usize, Fields<F, E>)>,
+    /// Number of variables
+    n_vars: usize,
+}
+
+impl<F: Field, E: ExtensionField<F>> SparseMultilinearPoly<F, E> {
+    /// Instantiates a `SparseMultilinearPoly` from (index, value) pairs, values sharing an
+    /// index are summed and zeros are dropped
+    pub fn new(n_vars: usize, entries: Vec<(usize, Fields<F, E>)>) -> Self {
+        let mut merged = BTreeMap::new();
+        for (index, value) in entries {
+            assert!(
+                index < 1 << n_vars,
+                "index {index} is outside the hypercube"
+            );
+            merged
+                .entry(index)
+                .and_modify(|acc: &mut Fields<F, E>| *acc += value)
+                .or_insert(value);
+        }
+
+        Self {
+            entries: merged
+                .into_iter()
+                .filter(|(_, value)| !value.to_extension_field().is_zero())
+                .collect(),
+            n_vars,
+        }
+    }
+
+    /// Creates a Zero sparse Multilinear poly
+    pub fn zero(n_vars: usize) -> Self {
+        Self {
+            entries: vec![],
+            n_vars,
+        }
+    }
+
+    /// Non-zero evaluations, sorted by index
+    pub fn entries(&self) -> &[(usize, Fields<F, E>)] {
+        &self.entries
+    }
+
+    /// Number of non-zero evaluations
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Dense table of all 2^n_vars evaluations
+    pub fn to_dense(&self) -> MultilinearPoly<F, E> {
+        let mut dense = MultilinearPoly::zero(self.n_vars);
+        for (index, value) in &self.entries {
+            dense.set(*index, *value);
+        }
+        dense
+    }
+
+    /// Partially fixes the first variables, returning the dense table of the remaining ones
+    pub fn partial_evaluate_to_dense(&self, points: &[Fields<F, E>]) -> MultilinearPoly<F, E> {
+        self.partial_evaluate(points).to_dense()
+    }
+
+    /// Scales every entry by the weight indexed by its first `k` variables and drops them
+    fn collapse_first_vars(&self, k: usize, weight: impl Fn(usize) -> Fields<F, E>) -> Self {
+        assert!(k <= self.n_vars);
+        let n_vars = self.n_vars - k;
+        let mask = (1 << n_vars) - 1;
+        let entries = self
+            .entries
+            .iter()
+            .map(|(index, value)| (index & mask, *value * weight(index >> n_vars)))
+            .collect();
+        Self::new(n_vars, entries)
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for SparseMultilinearPoly<F, E> {
+    /// Evaluates Σ value · eq(point, index) with the eq tables of both halves of the point,
+    /// O(nnz + 2^(n/2))
+    fn evaluate(&self, points: &[Fields<F, E>]) -> Fields<F, E> {
+        assert_eq!(self.n_vars, points.len());
+        let lo_vars = self.n_vars / 2;
+        let (hi, lo) = points.split_at(self.n_vars - lo_vars);
+        let (hi_eq, lo_eq) = (generate_eq(hi), generate_eq(lo));
+        let mask = (1 << lo_vars) - 1;
+
+        Fields::Extension(self.entries.iter().fold(E::zero(), |acc, (index, value)| {
+            acc + (hi_eq[index >> lo_vars] * lo_eq[index & mask] * *value).to_extension_field()
+        }))
+    }
+
+    /// Fixes the first variables, each entry is weighted by eq(points, its leading bits)
+    /// in O(nnz · points.len())
+    fn partial_evaluate(&self, points: &[Fields<F, E>]) -> Self {
+        let k = points.len();
+        self.collapse_first_vars(k, |prefix| {
+            Fields::Extension(points.iter().enumerate().fold(E::one(), |acc, (i, point)| {
+                let point = point.to_extension_field();
+                match (prefix >> (k - 1 - i)) & 1 {
+                    1 => acc * point,
+                    _ => acc * (E::one() - point),
+                }
+            }))
+        })
+    }
+
+    fn fold_first_vars(&self, weights: &[Fields<F, E>]) -> Self {
+        assert!(weights.len().is_power_of_two());
+        self.collapse_first_vars(weights.len().ilog2() as usize, |prefix| weights[prefix])
+    }
+
+    fn max_degree(&self) -> usize {
+        1
+    }
+
+    fn sum_over_hypercube(&self) -> Fields<F, E> {
+        Fields::Extension(self.entries.iter().fold(E::zero(), |acc, (_, value)| {
+            acc + value.to_extension_field()
+        }))
+    }
+
+    fn num_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.absorb_into(transcript);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for SparseMultilinearPoly<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.num_vars().absorb_into(transcript);
+        self.entries.absorb_into(transcript);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> From<&MultilinearPoly<F, E>> for SparseMultilinearPoly<F, E> {
+    /// Indices of an LSB-first table are bit-reversed into the MSB-first layout
+    fn from(dense: &MultilinearPoly<F, E>) -> Self {
+        let n_vars = dense.num_vars();
+        let index = |i: usize| match dense.order() {
+            VariableOrder::LsbFirst if n_vars > 0 => {
+                i.reverse_bits() >> (usize::BITS as usize - n_vars)
+            }
+            _ => i,
+        };
+        Self::new(
+            n_vars,
+            (0..1 << n_vars)
+                .map(|i| (index(i), dense.get(i)))
+                .filter(|(_, value)| !value.to_extension_field().is_zero())
+                .collect(),
+        )
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> From<&SparseMultilinearPoly<F, E>> for MultilinearPoly<F, E> {
+    fn from(sparse: &SparseMultilinearPoly<F, E>) -> Self {
+        sparse.to_dense()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::SparseMultilinearPoly;
+    use crate::{
+        Fields, MultilinearExtension,
+        mle::{MultilinearPoly, VariableOrder},
+    };
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn random_sparse(rng: &mut StdRng, n_vars: usize, nnz: usize) -> SparseMultilinearPoly<F, E> {
+        let entries = (0..nnz)
+            .map(|_| {
+                (
+                    rng.gen_range(0..1 << n_vars),
+                    Fields::Base(F::from_canonical_u32(rng.gen_range(1..1 << 30))),
+                )
+            })
+            .collect();
+        SparseMultilinearPoly::new(n_vars, entries)
+    }
+
+    fn random_point(rng: &mut StdRng, len: usize) -> Vec<Fields<F, E>> {
+        (0..len)
+            .map(|_| Fields::Extension(E::from_canonical_u32(rng.gen_range(0..1 << 30))))
+            .collect()
+    }
+
+    #[test]
+    fn test_new_merges_and_drops_zeros() {
+        let one = Fields::Base(F::one());
+        let sparse = SparseMultilinearPoly::<F, E>::new(
+            3,
+            vec![(5, one), (1, one), (5, one), (2, -one), (2, one)],
+        );
+        assert_eq!(sparse.entries(), &[(1, one), (5, Fields::Base(F::two()))]);
+
+        let dense = sparse.to_dense();
+        assert_eq!(dense.get(5).to_extension_field(), E::two());
+        assert_eq!(SparseMultilinearPoly::from(&dense), sparse);
+    }
+
+    #[test]
+    fn test_from_lsb_first() {
+        let values = [1, 2, 3, 4]
+            .map(|val| Fields::Base(F::from_canonical_u32(val)))
+            .to_vec();
+        let dense =
+            MultilinearPoly::<F, E>::new_from_vec(2, values).with_order(VariableOrder::LsbFirst);
+        let sparse = SparseMultilinearPoly::from(&dense);
+
+        let point = random_point(&mut StdRng::seed_from_u64(49), 2);
+        assert_eq!(
+            sparse.evaluate(&point).to_extension_field(),
+            dense.evaluate(&point).to_extension_field()
+        );
+        assert_eq!(
+            sparse.to_dense().evaluations().to_extension(),
+            [1, 3, 2, 4].map(E::from_canonical_u32).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_matches_dense() {
+        let mut rng = StdRng::seed_from_u64(48);
+        for n_vars in [1, 4, 7] {
+            let sparse = random_sparse(&mut rng, n_vars, 5);
+            let dense = MultilinearPoly::from(&sparse);
+            let point = random_point(&mut rng, n_vars);
+
+            assert_eq!(
+                sparse.evaluate(&point).to_extension_field(),
+                dense.evaluate(&point).to_extension_field()
+            );
+            assert_eq!(
+                sparse.sum_over_hypercube().to_extension_field(),
+                dense.sum_over_hypercube().to_extension_field()
+            );
+
+            let partial = sparse.partial_evaluate_to_dense(&point[..n_vars / 2 + 1]);
+            let expected = dense.partial_evaluate(&point[..n_vars / 2 + 1]);
+            assert_eq!(partial.num_vars(), expected.num_vars());
+            assert_eq!(
+                partial.evaluations().to_extension(),
+                expected.evaluations().to_extension()
+            );
+
+            let weights = random_point(&mut rng, 2);
+            let folded = sparse.fold_first_vars(&weights).to_dense();
+            assert_eq!(
+                folded.evaluations().to_extension(),
+                dense.fold_first_vars(&weights).evaluations().to_extension()
+            );
+        }
+    }
+}
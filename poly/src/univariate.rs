@@ -0,0 +1,314 @@
+//! Univariate polynomials, by coefficients or by evaluations.
+//! The evaluation form always lives on the domain {0, 1, ..., n - 1}, the convention of
+//! sumcheck round messages, polynomials over other domains are interpolated into
+//! coefficient form.
+use std::ops::{Add, Mul};
+
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
+
+use crate::{Fields, barycentric::BarycentricDomain, utils::barycentric_evaluation};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnivariatePoly<F: Field, E: ExtensionField<F>> {
+    /// c_0 + c_1 x + ... + c_{n-1} x^{n-1}
+    Coefficients(Vec<Fields<F, E>>),
+    /// p(0), p(1), ..., p(n - 1) of a polynomial of degree < n
+    Evaluations(Vec<Fields<F, E>>),
+}
+
+impl<F: Field, E: ExtensionField<F>> UnivariatePoly<F, E> {
+    pub fn from_coefficients(coefficients: Vec<Fields<F, E>>) -> Self {
+        Self::Coefficients(coefficients)
+    }
+
+    pub fn from_evaluations(evaluations: Vec<Fields<F, E>>) -> Self {
+        Self::Evaluations(evaluations)
+    }
+
+    /// Lagrange interpolation of the polynomial of degree < n taking `evaluations`
+    /// over a domain of n distinct points, in coefficient form
+    pub fn interpolate(domain: &[Fields<F, E>], evaluations: &[Fields<F, E>]) -> Self {
+        assert_eq!(domain.len(), evaluations.len());
+        let domain = domain
+            .iter()
+            .map(|point| point.to_extension_field())
+            .collect::<Vec<_>>();
+
+        // vanishing polynomial of the domain, lowest coefficient first
+        let mut vanishing = vec![E::one()];
+        for point in &domain {
+            let mut next = vec![E::zero(); vanishing.len() + 1];
+            for (i, coeff) in vanishing.iter().enumerate() {
+                next[i + 1] += *coeff;
+                next[i] -= *coeff * *point;
+            }
+            vanishing = next;
+        }
+
+        let mut coefficients = vec![E::zero(); domain.len()];
+        for (point, evaluation) in domain.iter().zip(evaluations) {
+            // vanishing / (x - point) by synthetic division
+            let mut quotient = vec![E::zero(); domain.len()];
+            let mut carry = E::zero();
+            for i in (0..domain.len()).rev() {
+                carry = vanishing[i + 1] + carry * *point;
+                quotient[i] = carry;
+            }
+            let denominator = horner(&quotient, *point);
+            let weight = evaluation.to_extension_field() * denominator.inverse();
+            for (coeff, q) in coefficients.iter_mut().zip(quotient) {
+                *coeff += weight * q;
+            }
+        }
+
+        Self::Coefficients(coefficients.into_iter().map(Fields::Extension).collect())
+    }
+
+    /// Number of stored coefficients or evaluations
+    pub fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values().is_empty()
+    }
+
+    /// Stored coefficients or evaluations
+    pub fn values(&self) -> &[Fields<F, E>] {
+        match self {
+            Self::Coefficients(values) | Self::Evaluations(values) => values,
+        }
+    }
+
+    /// Mutable access to the stored coefficients or evaluations
+    pub fn values_mut(&mut self) -> &mut Vec<Fields<F, E>> {
+        match self {
+            Self::Coefficients(values) | Self::Evaluations(values) => values,
+        }
+    }
+
+    /// Exact degree, `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients()
+            .iter()
+            .rposition(|coeff| coeff.to_extension_field() != E::zero())
+    }
+
+    /// Coefficients, lowest degree first
+    pub fn coefficients(&self) -> Vec<Fields<F, E>> {
+        match self {
+            Self::Coefficients(coefficients) => coefficients.clone(),
+            Self::Evaluations(evaluations) => {
+                Self::interpolate(&integer_domain(evaluations.len()), evaluations)
+                    .values()
+                    .to_vec()
+            }
+        }
+    }
+
+    /// Evaluations over {0, 1, ..., n - 1}, n being the number of stored values
+    pub fn evaluations(&self) -> Vec<Fields<F, E>> {
+        match self {
+            Self::Coefficients(coefficients) => integer_domain(coefficients.len())
+                .iter()
+                .map(|point| self.evaluate(point))
+                .collect(),
+            Self::Evaluations(evaluations) => evaluations.clone(),
+        }
+    }
+
+    /// Evaluates the polynomial, with Horner's rule in coefficient form
+    pub fn evaluate(&self, point: &Fields<F, E>) -> Fields<F, E> {
+        match self {
+            _ if self.is_empty() => Fields::Base(F::zero()),
+            Self::Coefficients(coefficients) => {
+                let coefficients = coefficients
+                    .iter()
+                    .map(|coeff| coeff.to_extension_field())
+                    .collect::<Vec<_>>();
+                Fields::Extension(horner(&coefficients, point.to_extension_field()))
+            }
+            Self::Evaluations(evaluations) => barycentric_evaluation(evaluations, point),
+        }
+    }
+
+    /// Evaluates the polynomial, in evaluation form reusing the weights of `domain`, which
+    /// must be `BarycentricDomain::integers(self.len())`, instead of rebuilding them
+    pub fn evaluate_on(
+        &self,
+        domain: &BarycentricDomain<F, E>,
+        point: &Fields<F, E>,
+    ) -> Fields<F, E> {
+        match self {
+            Self::Evaluations(evaluations) if !evaluations.is_empty() => {
+                domain.evaluate(evaluations, point)
+            }
+            _ => self.evaluate(point),
+        }
+    }
+}
+
+/// Evaluates coefficients, lowest degree first, at `point`
+fn horner<E: Field>(coefficients: &[E], point: E) -> E {
+    coefficients
+        .iter()
+        .rev()
+        .fold(E::zero(), |acc, coeff| acc * point + *coeff)
+}
+
+fn integer_domain<F: Field, E: ExtensionField<F>>(len: usize) -> Vec<Fields<F, E>> {
+    (0..len)
+        .map(|i| Fields::Base(F::from_canonical_usize(i)))
+        .collect()
+}
+
+impl<F: Field, E: ExtensionField<F>> Add for UnivariatePoly<F, E> {
+    type Output = Self;
+
+    /// Pointwise in evaluation form when both domains agree, in coefficient form otherwise
+    fn add(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (Self::Evaluations(lhs), Self::Evaluations(rhs)) if lhs.len() == rhs.len() => {
+                Self::Evaluations(lhs.into_iter().zip(rhs).map(|(l, r)| l + r).collect())
+            }
+            (lhs, rhs) => {
+                let (mut long, short) = match (lhs.coefficients(), rhs.coefficients()) {
+                    (lhs, rhs) if lhs.len() >= rhs.len() => (lhs, rhs),
+                    (lhs, rhs) => (rhs, lhs),
+                };
+                for (coeff, other) in long.iter_mut().zip(short) {
+                    *coeff += other;
+                }
+                Self::Coefficients(long)
+            }
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Mul for UnivariatePoly<F, E> {
+    type Output = Self;
+
+    /// Schoolbook product, in coefficient form
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = (self.coefficients(), rhs.coefficients());
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::Coefficients(vec![]);
+        }
+        let mut product = vec![Fields::Base(F::zero()); lhs.len() + rhs.len() - 1];
+        for (i, l) in lhs.iter().enumerate() {
+            for (j, r) in rhs.iter().enumerate() {
+                product[i + j] += *l * *r;
+            }
+        }
+        Self::Coefficients(product)
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> Mul<Fields<F, E>> for UnivariatePoly<F, E> {
+    type Output = Self;
+
+    /// Scales the polynomial, in either form
+    fn mul(mut self, scalar: Fields<F, E>) -> Self::Output {
+        for value in self.values_mut() {
+            *value = *value * scalar;
+        }
+        self
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for UnivariatePoly<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        let tag: usize = match self {
+            Self::Coefficients(_) => 0,
+            Self::Evaluations(_) => 1,
+        };
+        tag.absorb_into(transcript);
+        self.values().absorb_into(transcript);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use super::UnivariatePoly;
+    use crate::{Fields, barycentric::BarycentricDomain};
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn fields(values: &[u32]) -> Vec<Fields<F, E>> {
+        values
+            .iter()
+            .map(|val| Fields::Base(F::from_canonical_u32(*val)))
+            .collect()
+    }
+
+    fn ext(values: &[Fields<F, E>]) -> Vec<E> {
+        values.iter().map(|val| val.to_extension_field()).collect()
+    }
+
+    #[test]
+    fn test_coefficient_and_evaluation_forms_agree() {
+        // 5x^2 + 3x + 2
+        let coefficients = UnivariatePoly::from_coefficients(fields(&[2, 3, 5]));
+        let evaluations = UnivariatePoly::from_evaluations(fields(&[2, 10, 28]));
+
+        assert_eq!(ext(&coefficients.evaluations()), ext(evaluations.values()));
+        assert_eq!(ext(&evaluations.coefficients()), ext(coefficients.values()));
+        let domain = BarycentricDomain::integers(3);
+        for point in [0, 4, 1 << 20] {
+            let point = Fields::Base(F::from_canonical_u32(point));
+            let expected = coefficients.evaluate(&point).to_extension_field();
+            assert_eq!(evaluations.evaluate(&point).to_extension_field(), expected);
+            assert_eq!(
+                evaluations
+                    .evaluate_on(&domain, &point)
+                    .to_extension_field(),
+                expected
+            );
+            assert_eq!(
+                coefficients
+                    .evaluate_on(&domain, &point)
+                    .to_extension_field(),
+                expected
+            );
+        }
+        assert_eq!(coefficients.degree(), Some(2));
+        assert_eq!(
+            UnivariatePoly::from_evaluations(fields(&[7, 7, 7])).degree(),
+            Some(0)
+        );
+        assert_eq!(
+            UnivariatePoly::<F, E>::from_coefficients(fields(&[0, 0])).degree(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_interpolation_over_domain() {
+        // 5x^2 + 3x + 2 over {1, 4, 9}
+        let poly = UnivariatePoly::interpolate(&fields(&[1, 4, 9]), &fields(&[10, 94, 434]));
+        assert_eq!(ext(poly.values()), ext(&fields(&[2, 3, 5])));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // (x + 1) + (2x^2 + 3)
+        let lhs = UnivariatePoly::<F, E>::from_coefficients(fields(&[1, 1]));
+        let rhs = UnivariatePoly::from_evaluations(fields(&[3, 5, 11]));
+        let sum = lhs.clone() + rhs.clone();
+        assert_eq!(ext(sum.values()), ext(&fields(&[4, 1, 2])));
+
+        // (x + 1)(2x^2 + 3) = 2x^3 + 2x^2 + 3x + 3
+        let product = lhs * rhs.clone();
+        assert_eq!(ext(product.values()), ext(&fields(&[3, 3, 2, 2])));
+        assert_eq!(product.degree(), Some(3));
+
+        // evaluation forms of equal length stay in evaluation form
+        let doubled = rhs.clone() + rhs.clone();
+        assert!(matches!(doubled, UnivariatePoly::Evaluations(_)));
+        assert_eq!(doubled, rhs * Fields::Base(F::two()));
+    }
+}
@@ -1,49 +1,43 @@
-use std::rc::Rc;
-
 use p3_field::{ExtensionField, Field};
 
-use crate::{Fields, mle::MultilinearPoly, vpoly::VPoly};
+use crate::{
+    Fields, barycentric::BarycentricDomain, expr::Expr, mle::MultilinearPoly, vpoly::VPoly,
+};
 
-/// Evaluate a univariate polynomial in evaluation form
+/// Evaluate a univariate polynomial in evaluation form over the domain {0, 1, ..., n - 1},
+/// callers evaluating repeatedly should keep a `BarycentricDomain::integers(n)`
 pub fn barycentric_evaluation<F: Field, E: ExtensionField<F>>(
     evaluations: &[Fields<F, E>],
     evaluation_point: &Fields<F, E>,
 ) -> Fields<F, E> {
-    let m_x = (0..evaluations.len()).fold(E::one(), |mut acc, val| {
-        acc *= evaluation_point.to_extension_field() - E::from_canonical_usize(val);
-        acc
-    });
-
-    let mut res = E::zero();
-
-    for i in 0..evaluations.len() {
-        let numerator = evaluations[i].to_extension_field();
-
-        let di = (0..evaluations.len())
-            .filter(|val| *val != i)
-            .fold(E::one(), |mut acc, val| {
-                acc *= F::from_canonical_usize(i) - F::from_canonical_usize(val);
-                acc
-            });
-
-        let denominator = di * (evaluation_point.to_extension_field() - E::from_canonical_usize(i));
+    BarycentricDomain::integers(evaluations.len()).evaluate(evaluations, evaluation_point)
+}
 
-        res += numerator * denominator.inverse()
-    }
+/// Evaluate a univariate polynomial given by its evaluations over an arbitrary domain
+/// of distinct points, callers evaluating repeatedly should keep a `BarycentricDomain`
+pub fn barycentric_evaluation_on_domain<F: Field, E: ExtensionField<F>>(
+    evaluations: &[Fields<F, E>],
+    domain: &[Fields<F, E>],
+    evaluation_point: &Fields<F, E>,
+) -> Fields<F, E> {
+    BarycentricDomain::new(domain.to_vec()).evaluate(evaluations, evaluation_point)
+}
 
-    Fields::Extension(m_x * res)
+/// Evaluates every Lagrange basis polynomial of the domain at the given point
+/// L_i(x) = Π_{j != i} (x - x_j) / (x_i - x_j)
+pub fn lagrange_basis<F: Field, E: ExtensionField<F>>(
+    domain: &[Fields<F, E>],
+    evaluation_point: &Fields<F, E>,
+) -> Vec<Fields<F, E>> {
+    BarycentricDomain::new(domain.to_vec()).lagrange_basis(evaluation_point)
 }
 
 /// Helper function to build a Vpoly that combines via product
 pub fn product_poly<F: Field, E: ExtensionField<F>>(
     mles: Vec<MultilinearPoly<F, E>>,
 ) -> VPoly<F, E> {
-    let max_degree = mles.len();
-    VPoly::new(
-        mles,
-        max_degree,
-        Rc::new(|values: &[Fields<F, E>]| values.iter().cloned().product()),
-    )
+    let expr = Expr::Product((0..mles.len()).map(Expr::mle).collect());
+    VPoly::new_from_expr(mles, expr)
 }
 
 /// Generates eq(r, x) where eq(..) represents the multilinear extension of the identity polynomial
@@ -51,6 +45,20 @@ pub fn generate_eq<F: Field, E: ExtensionField<F>>(points: &[Fields<F, E>]) -> V
     let mut res = vec![Fields::Extension(E::one())];
 
     for point in points {
+        #[cfg(feature = "parallel")]
+        if res.len() >= crate::PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            let mut v = vec![Fields::Extension(E::zero()); res.len() * 2];
+            v.par_chunks_mut(2)
+                .zip(res.par_iter())
+                .for_each(|(pair, val)| {
+                    pair[0] = *val * (Fields::Extension(E::one()) - *point);
+                    pair[1] = *val * *point;
+                });
+            res = v;
+            continue;
+        }
+
         let mut v = vec![];
         for val in &res {
             v.push(*val * (Fields::Extension(E::one()) - *point));
@@ -92,5 +100,38 @@ mod tests {
             res,
             Fields::Extension(AbstractExtensionField::from_base(Mersenne31::new(142)))
         );
+
+        // evaluating on a domain point reads the evaluation back
+        let res = barycentric_evaluation(&poly, &Fields::Base(Mersenne31::new(3)));
+        assert_eq!(res.to_extension_field(), poly[3].to_extension_field());
+    }
+
+    #[test]
+    fn test_barycentric_evaluation_on_domain() {
+        // Polynomial in question: 5x^2 + 3x + 2 over the domain {1, 4, 9}
+        let domain: Vec<Fields<Mersenne31, BinomialExtensionField<Mersenne31, 3>>> = [1, 4, 9]
+            .into_iter()
+            .map(|val| Fields::Base(Mersenne31::new(val)))
+            .collect();
+        let poly = [10, 94, 434]
+            .into_iter()
+            .map(|val| Fields::Base(Mersenne31::new(val)))
+            .collect::<Vec<_>>();
+        let res =
+            barycentric_evaluation_on_domain(&poly, &domain, &Fields::Base(Mersenne31::new(5)));
+        assert_eq!(
+            res,
+            Fields::Extension(AbstractExtensionField::from_base(Mersenne31::new(142)))
+        );
+
+        // the basis sums to one everywhere
+        let basis = lagrange_basis(&domain, &Fields::Base(Mersenne31::new(7)));
+        assert_eq!(
+            basis
+                .into_iter()
+                .fold(Fields::Base(Mersenne31::new(0)), |acc, val| acc + val)
+                .to_extension_field(),
+            AbstractExtensionField::from_base(Mersenne31::new(1))
+        );
     }
 }
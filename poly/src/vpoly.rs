@@ -2,14 +2,18 @@
 //! A virtual polynomials is a Vector of MLEs having a combination relationship.
 use std::{
     fmt::{self, Debug, Formatter},
-    rc::Rc,
+    sync::Arc,
 };
 
+use crate::expr::Expr;
 use crate::mle::MultilinearPoly;
 use crate::{Fields, MultilinearExtension};
-use p3_field::{ExtensionField, Field, PrimeField32};
+use p3_field::{ExtensionField, Field};
+use transcript::{Transcript, TranscriptChallenger, absorb::TranscriptAbsorb};
 
-pub type CombineFn<F, E> = Rc<dyn Fn(&[Fields<F, E>]) -> Fields<F, E>>;
+/// Combination function over the MLE evaluations of a `VPoly`.
+/// It is `Send + Sync` so virtual polynomials can be shared across worker threads.
+pub type CombineFn<F, E> = Arc<dyn Fn(&[Fields<F, E>]) -> Fields<F, E> + Send + Sync>;
 
 #[derive(Clone)]
 pub struct VPoly<F: Field, E: ExtensionField<F>> {
@@ -21,6 +25,8 @@ pub struct VPoly<F: Field, E: ExtensionField<F>> {
     num_vars: usize,
     /// Combination function for evaluating the virtual polynomial.
     combine_fn: CombineFn<F, E>,
+    /// Symbolic form of the combination function, if the poly was built from one
+    expr: Option<Expr<F, E>>,
 }
 
 impl<F: Field + Debug, E: ExtensionField<F>> Debug for VPoly<F, E> {
@@ -30,6 +36,7 @@ impl<F: Field + Debug, E: ExtensionField<F>> Debug for VPoly<F, E> {
             .field("max_degree", &self.max_degree)
             .field("num_vars", &self.num_vars)
             .field("combine_fn", &"<function>") // Display placeholder for the function
+            .field("expr", &self.expr)
             .finish()
     }
 }
@@ -52,9 +59,22 @@ impl<F: Field, E: ExtensionField<F>> VPoly<F, E> {
             max_degree,
             num_vars,
             combine_fn,
+            expr: None,
         }
     }
 
+    /// Creates a new virtual polynomial from a vector of MLEs and a symbolic combine expression.
+    /// The max degree is inferred from the expression, which is compiled to the combine function.
+    pub fn new_from_expr(mles: Vec<MultilinearPoly<F, E>>, expr: Expr<F, E>) -> Self {
+        assert!(
+            expr.num_mles() <= mles.len(),
+            "expression references more MLEs than provided"
+        );
+        let mut vpoly = Self::new(mles, expr.degree(), expr.to_combine_fn());
+        vpoly.expr = Some(expr);
+        vpoly
+    }
+
     /// Poly max degree
     pub fn max_degree(&self) -> usize {
         self.max_degree
@@ -66,8 +86,18 @@ impl<F: Field, E: ExtensionField<F>> VPoly<F, E> {
     }
 
     /// MLEs in the polynomial
-    pub fn mles(&self) -> Vec<MultilinearPoly<F, E>> {
-        self.mles.clone()
+    pub fn mles(&self) -> &[MultilinearPoly<F, E>] {
+        &self.mles
+    }
+
+    /// Applies the combination function to one value per MLE
+    pub fn combine(&self, values: &[Fields<F, E>]) -> Fields<F, E> {
+        (self.combine_fn)(values)
+    }
+
+    /// Symbolic combine expression, if the poly was built from one
+    pub fn expr(&self) -> Option<&Expr<F, E>> {
+        self.expr.as_ref()
     }
 }
 
@@ -95,6 +125,32 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for VPoly<F, E>
             max_degree: self.max_degree,
             num_vars: self.num_vars - point.len(),
             combine_fn: self.combine_fn.clone(),
+            expr: self.expr.clone(),
+        }
+    }
+
+    /// Fixes the first variable of every MLE in place
+    fn fix_first_var_in_place(&mut self, point: &Fields<F, E>) {
+        for mle in self.mles.iter_mut() {
+            mle.fix_first_var_in_place(point);
+        }
+        self.num_vars -= 1;
+    }
+
+    /// Folds the first variables of every MLE with the given weights
+    fn fold_first_vars(&self, weights: &[Fields<F, E>]) -> Self {
+        let mles = self
+            .mles
+            .iter()
+            .map(|mle| mle.fold_first_vars(weights))
+            .collect::<Vec<_>>();
+
+        Self {
+            num_vars: mles[0].num_vars(),
+            mles,
+            max_degree: self.max_degree,
+            combine_fn: self.combine_fn.clone(),
+            expr: self.expr.clone(),
         }
     }
 
@@ -105,6 +161,23 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for VPoly<F, E>
 
     /// Returns the sum of evaluations over the boolean hypercube
     fn sum_over_hypercube(&self) -> Fields<F, E> {
+        #[cfg(feature = "parallel")]
+        if (1 << self.num_vars()) >= crate::PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            let sum = (0..(1 << self.num_vars()))
+                .into_par_iter()
+                .map(|i| {
+                    let row = self
+                        .mles
+                        .iter()
+                        .map(|p| p.get(i))
+                        .collect::<Vec<Fields<F, E>>>();
+                    (self.combine_fn)(&row).to_extension_field()
+                })
+                .reduce(E::zero, |acc, curr| acc + curr);
+            return Fields::Extension(sum);
+        }
+
         let mut sum = E::zero();
         for i in 0..(1 << self.num_vars()) {
             // TODO: get rid of the vec allocation here, maybe make
@@ -112,7 +185,7 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for VPoly<F, E>
             let row = self
                 .mles
                 .iter()
-                .map(|p| p[i])
+                .map(|p| p.get(i))
                 .collect::<Vec<Fields<F, E>>>();
             sum += (self.combine_fn)(&row).to_extension_field();
         }
@@ -125,16 +198,28 @@ impl<F: Field, E: ExtensionField<F>> MultilinearExtension<F, E> for VPoly<F, E>
     }
 
     /// Commit vpoly to transcript
-    fn commit_to_transcript(&self, transcript: &mut transcript::Transcript<F, E>)
-    where
-        F: PrimeField32,
-    {
-        // TODO: we need to add the combine function to the transcript
-        for mle in &self.mles {
-            mle.commit_to_transcript(transcript);
+    fn commit_to_transcript<C: TranscriptChallenger<F>>(
+        &self,
+        transcript: &mut Transcript<F, E, C>,
+    ) {
+        self.absorb_into(transcript);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for VPoly<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.mles.absorb_into(transcript);
+        // TODO: opaque combine functions are still not bound to the transcript,
+        //  build the poly with `new_from_expr` to bind the combination
+        match &self.expr {
+            Some(expr) => {
+                1_usize.absorb_into(transcript);
+                expr.absorb_into(transcript);
+            }
+            None => 0_usize.absorb_into(transcript),
         }
-        transcript.observe_base_element(&[F::from_canonical_usize(self.max_degree)]);
-        transcript.observe_base_element(&[F::from_canonical_usize(self.num_vars)]);
+        self.max_degree.absorb_into(transcript);
+        self.num_vars.absorb_into(transcript);
     }
 }
 
@@ -163,7 +248,7 @@ mod tests {
             3,
             vec![0, 0, 0, 3, 0, 0, 2, 5]
                 .into_iter()
-                .map(|val| (Fields::Base(F::from_canonical_u64(val))))
+                .map(|val| Fields::Base(F::from_canonical_u64(val)))
                 .collect(),
         )
     }
@@ -238,7 +323,7 @@ mod tests {
     #[test]
     fn test_eval_1() {
         let mles = vec![f_abc(), f_abc(), f_abc()];
-        let vpoly = VPoly::new(mles, 2, Rc::new(combined_fn_1)); // combination => 2(a * b) + c
+        let vpoly = VPoly::new(mles, 2, Arc::new(combined_fn_1)); // combination => 2(a * b) + c
         let points = vec![
             Fields::Base(F::from_canonical_u64(1)),
             Fields::Base(F::from_canonical_u64(2)),
@@ -248,10 +333,99 @@ mod tests {
         assert_eq!(vpoly.evaluate(&points), expected_mles);
     }
 
+    #[test]
+    fn test_vpoly_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<VPoly<F, E>>();
+    }
+
+    #[test]
+    fn test_shared_vpoly_across_threads() {
+        // 2 · f · f + f with f(i, b, c) over (b, c) being [0, 0, 2i, 3 + 2i],
+        // which sums to 16i² + 28i + 21
+        let vpoly = VPoly::new(vec![f_abc(), f_abc(), f_abc()], 2, Arc::new(combined_fn_1));
+        // large enough to take the threaded path when the `parallel` feature is enabled,
+        // 2 · g · g + g with g the table 0, 1, 2, ...
+        let g = MultilinearPoly::<F, E>::from_base(
+            13,
+            (0..1 << 13).map(F::from_canonical_u64).collect(),
+        );
+        let large = VPoly::new(vec![g.clone(), g.clone(), g], 2, Arc::new(combined_fn_1));
+        let large_sum = (0..1 << 13)
+            .map(F::from_canonical_u64)
+            .fold(F::zero(), |acc, j| acc + F::two() * j * j + j);
+
+        let sums = std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|i| {
+                    let (vpoly, large) = (&vpoly, &large);
+                    scope.spawn(move || {
+                        (
+                            vpoly
+                                .partial_evaluate(&[Fields::Base(F::from_canonical_u64(i))])
+                                .sum_over_hypercube(),
+                            large.sum_over_hypercube(),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        for (i, (sum, large_thread_sum)) in sums.into_iter().enumerate() {
+            let i = F::from_canonical_u64(i as u64);
+            let expected = F::from_canonical_u64(16) * i * i
+                + F::from_canonical_u64(28) * i
+                + F::from_canonical_u64(21);
+            assert_eq!(
+                sum.to_extension_field(),
+                Fields::<F, E>::Base(expected).to_extension_field()
+            );
+            assert_eq!(
+                large_thread_sum.to_extension_field(),
+                Fields::<F, E>::Base(large_sum).to_extension_field()
+            );
+            assert_eq!(large_thread_sum, large.sum_over_hypercube());
+        }
+    }
+
+    #[test]
+    fn test_vpoly_from_expr() {
+        // combination => 2(a * b) + c
+        let expr =
+            Expr::constant(Fields::Base(F::from_canonical_u64(2))) * Expr::mle(0) * Expr::mle(1)
+                + Expr::mle(2);
+        let vpoly = VPoly::new_from_expr(vec![f_abc(), f_abc(), f_abc()], expr);
+        assert_eq!(vpoly.max_degree(), 2);
+
+        let closure_vpoly = VPoly::new(vec![f_abc(), f_abc(), f_abc()], 2, Arc::new(combined_fn_1));
+        let points = vec![
+            Fields::Base(F::from_canonical_u64(1)),
+            Fields::Base(F::from_canonical_u64(2)),
+            Fields::Base(F::from_canonical_u64(3)),
+        ];
+        assert_eq!(vpoly.evaluate(&points), closure_vpoly.evaluate(&points));
+        assert_eq!(
+            vpoly.partial_evaluate(&points[..1]).sum_over_hypercube(),
+            closure_vpoly
+                .partial_evaluate(&points[..1])
+                .sum_over_hypercube()
+        );
+    }
+
+    #[test]
+    #[should_panic = "expression references more MLEs than provided"]
+    fn test_vpoly_from_expr_missing_mle() {
+        let _ = VPoly::new_from_expr(vec![f_abc()], Expr::mle(0) * Expr::mle(1));
+    }
+
     #[test]
     fn test_sum_over_boolean_hypercube() {
         let mles = vec![f_abc(), f_abc(), f_abc()];
-        let vpoly = VPoly::new(mles, 2, Rc::new(combined_fn_1)); // combination => 2(a * b) + c
+        let vpoly = VPoly::new(mles, 2, Arc::new(combined_fn_1)); // combination => 2(a * b) + c
         assert_eq!(
             vpoly.sum_over_hypercube(),
             Fields::Extension(E::from_canonical_u64(86))
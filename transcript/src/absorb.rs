@@ -0,0 +1,141 @@
+//! Structured absorption into the transcript.
+//! Every encoding is unambiguous: integers are four 16-bit limbs whatever their type,
+//! sequences are prefixed with their length, and `Fields` are always absorbed as
+//! extension elements so that the representation of a value does not change the transcript.
+use fields::Fields;
+use p3_field::{ExtensionField, Field};
+
+use crate::{Transcript, TranscriptChallenger};
+
+/// Data that can be bound to a transcript
+pub trait TranscriptAbsorb<F: Field, E: ExtensionField<F>> {
+    /// Absorbs the encoding of `self`
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>);
+}
+
+/// The four 16-bit limbs, least significant first, an integer is absorbed as
+pub(crate) fn integer_limbs<F: Field>(value: u64) -> [F; 4] {
+    [0, 16, 32, 48].map(|shift| F::from_canonical_u64((value >> shift) & 0xffff))
+}
+
+macro_rules! impl_absorb_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for $int {
+                fn absorb_into<C: TranscriptChallenger<F>>(
+                    &self,
+                    transcript: &mut Transcript<F, E, C>,
+                ) {
+                    transcript.observe_base_element(&integer_limbs(*self as u64));
+                }
+            }
+        )*
+    };
+}
+
+impl_absorb_for_int!(u32, u64, usize);
+
+/// Byte string, absorbed length-prefixed with one element per byte
+#[derive(Debug, Clone, Copy)]
+pub struct Bytes<'a>(pub &'a [u8]);
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for Bytes<'_> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.0.len().absorb_into(transcript);
+        transcript.observe_base_element(
+            &self
+                .0
+                .iter()
+                .map(|byte| F::from_canonical_u8(*byte))
+                .collect::<Vec<_>>(),
+        );
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptAbsorb<F, E> for Fields<F, E> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        transcript.observe_ext_element(&[self.to_extension_field()]);
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, T: TranscriptAbsorb<F, E>> TranscriptAbsorb<F, E> for [T] {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.len().absorb_into(transcript);
+        for item in self {
+            item.absorb_into(transcript);
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, T: TranscriptAbsorb<F, E>> TranscriptAbsorb<F, E> for Vec<T> {
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.as_slice().absorb_into(transcript);
+    }
+}
+
+impl<F, E, A, B> TranscriptAbsorb<F, E> for (A, B)
+where
+    F: Field,
+    E: ExtensionField<F>,
+    A: TranscriptAbsorb<F, E>,
+    B: TranscriptAbsorb<F, E>,
+{
+    fn absorb_into<C: TranscriptChallenger<F>>(&self, transcript: &mut Transcript<F, E, C>) {
+        self.0.absorb_into(transcript);
+        self.1.absorb_into(transcript);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fields::Fields;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use super::{Bytes, TranscriptAbsorb};
+    use crate::Transcript;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn challenge<T: TranscriptAbsorb<F, E> + ?Sized>(value: &T) -> E {
+        let mut transcript = Transcript::<F, E>::init();
+        transcript.absorb(b"value", value);
+        transcript.challenge(b"challenge")
+    }
+
+    #[test]
+    fn test_integer_encoding_is_type_independent() {
+        assert_eq!(challenge(&7_u32), challenge(&7_usize));
+        assert_eq!(challenge(&7_u64), challenge(&7_usize));
+        // values above the field modulus do not wrap
+        assert_ne!(challenge(&(1_u64 << 31)), challenge(&1_u64));
+        assert_ne!(challenge(&u64::MAX), challenge(&(u64::MAX - (1 << 31) + 1)));
+    }
+
+    #[test]
+    fn test_fields_encoding_is_representation_independent() {
+        let base = Fields::<F, E>::Base(F::from_canonical_u32(9));
+        let extension = Fields::<F, E>::Extension(E::from_canonical_u32(9));
+        assert_eq!(challenge(&base), challenge(&extension));
+    }
+
+    #[test]
+    fn test_sequences_are_length_prefixed() {
+        let values = |vals: &[u32]| {
+            vals.iter()
+                .map(|val| Fields::<F, E>::Base(F::from_canonical_u32(*val)))
+                .collect::<Vec<_>>()
+        };
+
+        // [[1, 2], [3]] and [[1], [2, 3]] flatten to the same elements
+        assert_ne!(
+            challenge(&vec![values(&[1, 2]), values(&[3])]),
+            challenge(&vec![values(&[1]), values(&[2, 3])])
+        );
+        assert_ne!(challenge(&Bytes(b"ab")), challenge(&Bytes(b"abc")));
+        assert_eq!(
+            challenge(&values(&[4, 5])),
+            challenge(values(&[4, 5]).as_slice())
+        );
+    }
+}
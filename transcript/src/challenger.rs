@@ -0,0 +1,139 @@
+//! Keccak challenger for 64-bit prime fields.
+//! Elements are observed as their canonical 8-byte little-endian encoding, samples are
+//! drawn by rejection over the smallest power of two covering the field order.
+use std::marker::PhantomData;
+
+use p3_challenger::{
+    CanObserve, CanSample, CanSampleBits, FieldChallenger, GrindingChallenger, HashChallenger,
+};
+use p3_field::PrimeField64;
+use p3_keccak::Keccak256Hash;
+
+/// Proof-of-work bits above which grinding is refused
+pub const MAX_GRINDING_BITS: usize = 40;
+/// Extra bits of search space past `2^bits`, a witness is missed with probability e^(-2^8)
+const GRINDING_SLACK_BITS: usize = 8;
+/// Candidates checked by each worker between checks for a witness found by another
+#[cfg(feature = "parallel")]
+const GRINDING_CHUNK: u64 = 1 << 10;
+
+/// Keccak challenger serialising 64-bit field elements, e.g. for Goldilocks
+#[derive(Clone)]
+pub struct KeccakChallenger64<F> {
+    inner: HashChallenger<u8, Keccak256Hash, 32>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField64> KeccakChallenger64<F> {
+    /// Instantiates the challenger with an empty initial state
+    pub fn new() -> Self {
+        Self {
+            inner: HashChallenger::new(vec![], Keccak256Hash),
+            _marker: PhantomData,
+        }
+    }
+
+    fn sample_u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.inner.sample_array::<8>())
+    }
+}
+
+impl<F: PrimeField64> Default for KeccakChallenger64<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField64> CanObserve<F> for KeccakChallenger64<F> {
+    fn observe(&mut self, value: F) {
+        self.inner
+            .observe_slice(&value.as_canonical_u64().to_le_bytes());
+    }
+}
+
+impl<F: PrimeField64> CanSample<F> for KeccakChallenger64<F> {
+    fn sample(&mut self) -> F {
+        let bits = u64::BITS - (F::ORDER_U64 - 1).leading_zeros();
+        let mask = u64::MAX >> (u64::BITS - bits);
+        loop {
+            let value = self.sample_u64() & mask;
+            if value < F::ORDER_U64 {
+                return F::from_canonical_u64(value);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField64> CanSampleBits<usize> for KeccakChallenger64<F> {
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        assert!(bits < usize::BITS as usize, "too many bits requested");
+        (self.sample_u64() as usize) & ((1 << bits) - 1)
+    }
+}
+
+impl<F: PrimeField64> FieldChallenger<F> for KeccakChallenger64<F> {}
+
+impl<F: PrimeField64> GrindingChallenger for KeccakChallenger64<F> {
+    type Witness = F;
+
+    /// Finds the smallest witness, scanning 2^(bits + 8) candidates from a single snapshot
+    /// of the state. With the `parallel` feature the scan is split between threads, and
+    /// still returns the smallest witness.
+    fn grind(&mut self, bits: usize) -> F {
+        assert!(
+            bits <= MAX_GRINDING_BITS,
+            "at most {MAX_GRINDING_BITS} proof-of-work bits are supported"
+        );
+        let snapshot = self.clone();
+        let is_witness = |candidate: u64| {
+            snapshot
+                .clone()
+                .check_witness(bits, F::from_canonical_u64(candidate))
+        };
+        let bound = F::ORDER_U64.min(1 << (bits + GRINDING_SLACK_BITS));
+
+        #[cfg(feature = "parallel")]
+        let witness = {
+            use rayon::prelude::*;
+            (0..bound.div_ceil(GRINDING_CHUNK))
+                .into_par_iter()
+                .find_map_first(|chunk| {
+                    let start = chunk * GRINDING_CHUNK;
+                    (start..bound.min(start + GRINDING_CHUNK))
+                        .find(|candidate| is_witness(*candidate))
+                })
+        };
+        #[cfg(not(feature = "parallel"))]
+        let witness = (0..bound).find(|candidate| is_witness(*candidate));
+
+        // every candidate failing has probability e^(-2^8)
+        let witness = F::from_canonical_u64(
+            witness.unwrap_or_else(|| panic!("no proof-of-work witness among {bound} candidates")),
+        );
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_challenger::{CanObserve, CanSample};
+    use p3_field::{AbstractField, PrimeField64};
+    use p3_goldilocks::Goldilocks;
+
+    use super::KeccakChallenger64;
+
+    #[test]
+    fn test_samples_cover_full_width() {
+        let mut challenger = KeccakChallenger64::<Goldilocks>::new();
+        challenger.observe(Goldilocks::from_canonical_u64(1 << 40));
+
+        // a 32-bit truncation would keep every sample below 2^32
+        let samples: Vec<Goldilocks> = (0..16).map(|_| challenger.sample()).collect();
+        assert!(
+            samples
+                .iter()
+                .any(|sample| sample.as_canonical_u64() >= 1 << 32)
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Prover and verifier channels.
+//! A channel carries prover messages to the verifier and verifier challenges back
+//! to the prover. `Transcript` is the Fiat-Shamir channel, where challenges are
+//! derived from the messages. The interactive channel exchanges messages with a
+//! real counterparty over an in-memory pipe, with the verifier picking challenges.
+//! Labels domain-separate the Fiat-Shamir transcript, the interactive channel ignores them.
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use fields::Fields;
+use p3_field::{ExtensionField, Field};
+
+use crate::{Transcript, TranscriptChallenger};
+
+/// Prover side of a channel, sending and receiving fail once the verifier is gone
+pub trait ProverChannel<F: Field, E: ExtensionField<F>> {
+    /// Sends a labelled prover message to the verifier
+    fn send_message(&mut self, label: &[u8], message: &[Fields<F, E>]) -> anyhow::Result<()>;
+
+    /// Receives the next verifier challenge
+    fn receive_challenge(&mut self, label: &[u8]) -> anyhow::Result<E>;
+}
+
+/// Verifier side of a channel
+pub trait VerifierChannel<F: Field, E: ExtensionField<F>> {
+    /// Receives the next prover message, `None` once the prover has nothing left to send
+    fn receive_message(&mut self, label: &[u8]) -> Option<Vec<Fields<F, E>>>;
+
+    /// Picks the next challenge and sends it to the prover
+    fn send_challenge(&mut self, label: &[u8]) -> E;
+}
+
+impl<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>> ProverChannel<F, E>
+    for Transcript<F, E, C>
+{
+    fn send_message(&mut self, label: &[u8], message: &[Fields<F, E>]) -> anyhow::Result<()> {
+        self.append_message(label, message);
+        Ok(())
+    }
+
+    fn receive_challenge(&mut self, label: &[u8]) -> anyhow::Result<E> {
+        Ok(self.challenge(label))
+    }
+}
+
+/// Fiat-Shamir verifier channel, replays the prover messages of a proof through a transcript
+pub struct FiatShamirVerifierChannel<'a, F: Field, E: ExtensionField<F>, C> {
+    transcript: &'a mut Transcript<F, E, C>,
+    messages: std::slice::Iter<'a, Vec<Fields<F, E>>>,
+}
+
+impl<'a, F: Field, E: ExtensionField<F>, C> FiatShamirVerifierChannel<'a, F, E, C> {
+    /// Instantiates a channel reading `messages` in order
+    pub fn new(transcript: &'a mut Transcript<F, E, C>, messages: &'a [Vec<Fields<F, E>>]) -> Self {
+        Self {
+            transcript,
+            messages: messages.iter(),
+        }
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>> VerifierChannel<F, E>
+    for FiatShamirVerifierChannel<'_, F, E, C>
+{
+    fn receive_message(&mut self, label: &[u8]) -> Option<Vec<Fields<F, E>>> {
+        let message = self.messages.next()?;
+        self.transcript.append_message(label, message);
+        Some(message.clone())
+    }
+
+    fn send_challenge(&mut self, label: &[u8]) -> E {
+        self.transcript.challenge(label)
+    }
+}
+
+/// Prover endpoint of an interactive channel
+pub struct InteractiveProverChannel<F: Field, E: ExtensionField<F>> {
+    messages: Sender<Vec<Fields<F, E>>>,
+    challenges: Receiver<E>,
+}
+
+/// Verifier endpoint of an interactive channel, challenges come from `sampler`
+pub struct InteractiveVerifierChannel<F: Field, E: ExtensionField<F>, S: FnMut() -> E> {
+    messages: Receiver<Vec<Fields<F, E>>>,
+    challenges: Sender<E>,
+    sampler: S,
+}
+
+/// Creates a connected pair of interactive endpoints, the endpoints can be moved
+/// to different threads
+pub fn interactive_channel<F: Field, E: ExtensionField<F>, S: FnMut() -> E>(
+    sampler: S,
+) -> (
+    InteractiveProverChannel<F, E>,
+    InteractiveVerifierChannel<F, E, S>,
+) {
+    let (message_sender, message_receiver) = channel();
+    let (challenge_sender, challenge_receiver) = channel();
+    (
+        InteractiveProverChannel {
+            messages: message_sender,
+            challenges: challenge_receiver,
+        },
+        InteractiveVerifierChannel {
+            messages: message_receiver,
+            challenges: challenge_sender,
+            sampler,
+        },
+    )
+}
+
+impl<F: Field, E: ExtensionField<F>> ProverChannel<F, E> for InteractiveProverChannel<F, E> {
+    fn send_message(&mut self, _label: &[u8], message: &[Fields<F, E>]) -> anyhow::Result<()> {
+        self.messages
+            .send(message.to_vec())
+            .map_err(|_| anyhow::anyhow!("verifier endpoint disconnected"))
+    }
+
+    fn receive_challenge(&mut self, _label: &[u8]) -> anyhow::Result<E> {
+        self.challenges
+            .recv()
+            .map_err(|_| anyhow::anyhow!("verifier endpoint disconnected"))
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, S: FnMut() -> E> VerifierChannel<F, E>
+    for InteractiveVerifierChannel<F, E, S>
+{
+    fn receive_message(&mut self, _label: &[u8]) -> Option<Vec<Fields<F, E>>> {
+        self.messages.recv().ok()
+    }
+
+    fn send_challenge(&mut self, _label: &[u8]) -> E {
+        let challenge = (self.sampler)();
+        // the prover may already be done, the challenge is still used by the verifier
+        let _ = self.challenges.send(challenge);
+        challenge
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use fields::Fields;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use super::{FiatShamirVerifierChannel, ProverChannel, VerifierChannel, interactive_channel};
+    use crate::Transcript;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn message(val: u32) -> Vec<Fields<F, E>> {
+        vec![Fields::Base(F::from_canonical_u32(val))]
+    }
+
+    #[test]
+    fn test_fiat_shamir_channels_agree() {
+        let messages = vec![message(1), message(2)];
+
+        let mut prover = Transcript::<F, E>::init();
+        let mut prover_challenges = vec![];
+        for message in &messages {
+            prover.send_message(b"msg", message).unwrap();
+            prover_challenges.push(prover.receive_challenge(b"chal").unwrap());
+        }
+
+        let mut transcript = Transcript::init();
+        let mut verifier = FiatShamirVerifierChannel::new(&mut transcript, &messages);
+        for (message, challenge) in messages.iter().zip(prover_challenges) {
+            assert_eq!(verifier.receive_message(b"msg").as_ref(), Some(message));
+            assert_eq!(verifier.send_challenge(b"chal"), challenge);
+        }
+        assert_eq!(verifier.receive_message(b"msg"), None);
+    }
+
+    #[test]
+    fn test_interactive_channel_across_threads() {
+        let mut next = 0;
+        let (mut prover, mut verifier) = interactive_channel::<F, E, _>(move || {
+            next += 1;
+            E::from_canonical_u32(next)
+        });
+
+        let handle = thread::spawn(move || {
+            (1..=3)
+                .map(|val| {
+                    prover.send_message(b"msg", &message(val)).unwrap();
+                    prover.receive_challenge(b"chal").unwrap()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        for val in 1..=3 {
+            assert_eq!(verifier.receive_message(b"msg"), Some(message(val)));
+            assert_eq!(verifier.send_challenge(b"chal"), E::from_canonical_u32(val));
+        }
+        assert_eq!(
+            handle.join().unwrap(),
+            (1..=3).map(E::from_canonical_u32).collect::<Vec<_>>()
+        );
+        // the prover endpoint is dropped once it is done
+        assert_eq!(verifier.receive_message(b"msg"), None);
+    }
+
+    #[test]
+    fn test_interactive_prover_errors_once_verifier_is_gone() {
+        let (mut prover, verifier) = interactive_channel::<F, E, _>(E::zero);
+        drop(verifier);
+        assert!(prover.send_message(b"msg", &message(1)).is_err());
+        assert!(prover.receive_challenge(b"chal").is_err());
+    }
+}
@@ -1,24 +1,146 @@
+pub mod absorb;
+pub mod challenger;
+pub mod channel;
+#[cfg(feature = "poseidon2")]
+pub mod poseidon2;
+pub mod replay;
+
 use std::marker::PhantomData;
 
+use absorb::TranscriptAbsorb;
+pub use challenger::KeccakChallenger64;
 use fields::Fields;
-use p3_challenger::{CanObserve, FieldChallenger, HashChallenger, SerializingChallenger32};
-use p3_field::{ExtensionField, Field, PrimeField32};
+use p3_challenger::{FieldChallenger, GrindingChallenger, HashChallenger, SerializingChallenger32};
+use p3_field::{ExtensionField, Field, PrimeField32, PrimeField64};
 use p3_keccak::Keccak256Hash;
+use replay::{TranscriptEvent, TranscriptLog};
+
+/// Keccak challenger serialising 32-bit field elements, the default transcript backend
+pub type KeccakChallenger32<F> = SerializingChallenger32<F, HashChallenger<u8, Keccak256Hash, 32>>;
+
+/// Challenger backing a `Transcript`
+pub trait TranscriptChallenger<F: Field>:
+    FieldChallenger<F> + GrindingChallenger<Witness = F> + Clone
+{
+    /// Instantiates the challenger in its initial state
+    fn init() -> Self;
+}
+
+impl<F: PrimeField32> TranscriptChallenger<F> for KeccakChallenger32<F> {
+    fn init() -> Self {
+        SerializingChallenger32::new(HashChallenger::new(vec![], Keccak256Hash))
+    }
+}
 
-pub struct Transcript<F: Field, E: ExtensionField<F>> {
+impl<F: PrimeField64> TranscriptChallenger<F> for KeccakChallenger64<F> {
+    fn init() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct Transcript<F: Field, E: ExtensionField<F>, C = KeccakChallenger32<F>> {
     _marker: PhantomData<(F, E)>,
-    challenger: SerializingChallenger32<F, HashChallenger<u8, Keccak256Hash, 32>>,
+    challenger: C,
+    /// Events recorded since `start_recording`
+    log: Option<TranscriptLog<F, E>>,
+}
+
+/// Saved state of a transcript, see `Transcript::snapshot`
+#[derive(Clone)]
+pub struct TranscriptSnapshot<C> {
+    challenger: C,
+    /// Number of logged events, zero when the transcript was not recording
+    log_len: usize,
+}
+
+impl<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>> Default for Transcript<F, E, C> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<F: Field + PrimeField32, E: ExtensionField<F>> Transcript<F, E> {
-    // Instantiate a transcript with a challenger
+    // Instantiate a transcript with the default 32-bit challenger
     pub fn init() -> Self {
+        Self::new()
+    }
+
+    // Instantiate a transcript with the default 32-bit challenger, separated to a protocol
+    pub fn init_with_domain(protocol_id: &[u8]) -> Self {
+        Self::new_with_domain(protocol_id)
+    }
+}
+
+impl<F: Field, E: ExtensionField<F>, C: TranscriptChallenger<F>> Transcript<F, E, C> {
+    // Instantiate a transcript with any challenger
+    pub fn new() -> Self {
         Self {
             _marker: PhantomData,
-            challenger: SerializingChallenger32::new(HashChallenger::new(vec![], Keccak256Hash)),
+            challenger: C::init(),
+            log: None,
+        }
+    }
+
+    // Starts logging every label, observed element and challenge
+    pub fn start_recording(&mut self) {
+        self.log.get_or_insert_with(TranscriptLog::new);
+    }
+
+    // Returns the recorded events, `None` if the transcript is not recording
+    pub fn log(&self) -> Option<&[TranscriptEvent<F, E>]> {
+        self.log.as_ref().map(|log| log.events.as_slice())
+    }
+
+    // Returns an independent copy of the transcript, including its log
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    // Saves the current state of the transcript
+    pub fn snapshot(&self) -> TranscriptSnapshot<C> {
+        TranscriptSnapshot {
+            challenger: self.challenger.clone(),
+            log_len: self.log.as_ref().map_or(0, |log| log.events.len()),
         }
     }
 
+    // Rolls the transcript back to a snapshot taken from it, dropping later log events.
+    // Events recorded after a snapshot taken before `start_recording` are all dropped.
+    pub fn restore(&mut self, snapshot: &TranscriptSnapshot<C>) {
+        self.challenger = snapshot.challenger.clone();
+        if let Some(log) = self.log.as_mut() {
+            log.truncate(snapshot.log_len);
+        }
+    }
+
+    // Instantiate a transcript with any challenger, separated to a protocol
+    pub fn new_with_domain(protocol_id: &[u8]) -> Self {
+        let mut transcript = Self::new();
+        transcript.absorb_label(b"domain");
+        transcript.absorb_label(protocol_id);
+        transcript
+    }
+
+    // Absorbs a labelled, length-prefixed message
+    pub fn append_message(&mut self, label: &[u8], message: &[Fields<F, E>]) {
+        self.absorb_label(label);
+        message.len().absorb_into(self);
+        self.observe(message);
+    }
+
+    // Absorbs a labelled value with its structured encoding
+    pub fn absorb<T: TranscriptAbsorb<F, E> + ?Sized>(&mut self, label: &[u8], value: &T) {
+        self.absorb_label(label);
+        value.absorb_into(self);
+    }
+
+    // Samples a labelled challenge in the extension field
+    pub fn challenge(&mut self, label: &[u8]) -> E {
+        self.absorb_label(label);
+        self.sample_challenge()
+    }
+
     // Absorbs a byte array to the transcript
     pub fn observe(&mut self, vals: &[Fields<F, E>]) {
         for val in vals {
@@ -34,6 +156,9 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>> Transcript<F, E> {
     pub fn observe_base_element(&mut self, vals: &[F]) {
         for val in vals {
             self.challenger.observe(*val);
+            if let Some(log) = self.log.as_mut() {
+                log.observe(Fields::Base(*val));
+            }
         }
     }
 
@@ -41,12 +166,86 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>> Transcript<F, E> {
     pub fn observe_ext_element(&mut self, vals: &[E]) {
         for val in vals {
             self.challenger.observe_ext_element(*val);
+            if let Some(log) = self.log.as_mut() {
+                log.observe(Fields::Extension(*val));
+            }
+        }
+    }
+
+    // Absorbs a length-prefixed label, byte by byte
+    fn absorb_label(&mut self, label: &[u8]) {
+        if let Some(log) = self.log.as_mut() {
+            log.label(label);
+        }
+        for limb in absorb::integer_limbs(label.len() as u64) {
+            self.challenger.observe(limb);
+        }
+        for byte in label {
+            self.challenger.observe(F::from_canonical_u8(*byte));
         }
     }
 
     // Samples a random challenge in the extension field
     pub fn sample_challenge(&mut self) -> E {
-        self.challenger.sample_ext_element()
+        let challenge = self.challenger.sample_ext_element();
+        if let Some(log) = self.log.as_mut() {
+            log.challenge(Fields::Extension(challenge));
+        }
+        challenge
+    }
+
+    // Samples a random element from the base field
+    pub fn sample_base(&mut self) -> F {
+        let challenge = self.challenger.sample();
+        if let Some(log) = self.log.as_mut() {
+            log.challenge(Fields::Base(challenge));
+        }
+        challenge
+    }
+
+    // Samples a uniformly random integer of `bits` bits, `bits` must be below the field's
+    // bit length as the challengers sample at most one field element's worth of bits
+    pub fn sample_bits(&mut self, bits: usize) -> usize {
+        assert!(
+            bits < F::bits(),
+            "cannot sample {bits} bits from a {}-bit field",
+            F::bits()
+        );
+        let sample = self.challenger.sample_bits(bits);
+        if let Some(log) = self.log.as_mut() {
+            log.index(sample);
+        }
+        sample
+    }
+
+    // Samples a uniformly random index in 0..bound, by rejection over the next power of two.
+    // `bound` is at most 2^(F::bits() - 1), e.g. 2^30 over Mersenne31 or BabyBear
+    pub fn sample_index(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "cannot sample from an empty range");
+        let bits = bound.next_power_of_two().trailing_zeros() as usize;
+        loop {
+            let index = self.sample_bits(bits);
+            if index < bound {
+                return index;
+            }
+        }
+    }
+
+    // Finds a proof-of-work witness for `pow_bits` bits and absorbs it
+    pub fn grind(&mut self, pow_bits: usize) -> F {
+        let witness = self.challenger.grind(pow_bits);
+        if let Some(log) = self.log.as_mut() {
+            log.observe(Fields::Base(witness));
+        }
+        witness
+    }
+
+    // Absorbs a proof-of-work witness, returns whether it is valid for `pow_bits` bits
+    pub fn check_witness(&mut self, pow_bits: usize, witness: F) -> bool {
+        if let Some(log) = self.log.as_mut() {
+            log.observe(Fields::Base(witness));
+        }
+        self.challenger.check_witness(pow_bits, witness)
     }
 
     // Samples n element from the extension field
@@ -54,7 +253,7 @@ impl<F: Field + PrimeField32, E: ExtensionField<F>> Transcript<F, E> {
         let mut res = Vec::with_capacity(n);
 
         for _ in 0..n {
-            res.push(self.challenger.sample_ext_element());
+            res.push(self.sample_challenge());
         }
 
         res
@@ -67,6 +266,7 @@ pub mod tests {
     use p3_mersenne_31::Mersenne31;
 
     use crate::Transcript;
+    use fields::Fields;
 
     #[test]
     fn test_transcript_initialization() {
@@ -93,4 +293,173 @@ pub mod tests {
 
         transcript.observe_ext_element(&challenge);
     }
+
+    #[test]
+    fn test_labels_separate_messages() {
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        let message = [Fields::<Mersenne31, E>::Base(
+            Mersenne31::from_canonical_u32(7),
+        )];
+
+        let challenge = |domain: &[u8], label: &[u8]| {
+            let mut transcript = Transcript::<Mersenne31, E>::init_with_domain(domain);
+            transcript.append_message(label, &message);
+            transcript.challenge(b"challenge")
+        };
+
+        assert_eq!(challenge(b"sumcheck", b"a"), challenge(b"sumcheck", b"a"));
+        assert_ne!(challenge(b"sumcheck", b"a"), challenge(b"sumcheck", b"b"));
+        assert_ne!(challenge(b"sumcheck", b"a"), challenge(b"gkr", b"a"));
+
+        // the label length is absorbed, so labels cannot run into the message
+        let mut transcript = Transcript::<Mersenne31, E>::init();
+        transcript.append_message(b"ab", &[]);
+        let mut other = Transcript::<Mersenne31, E>::init();
+        other.append_message(b"a", &[Fields::Base(Mersenne31::from_canonical_u8(b'b'))]);
+        assert_ne!(transcript.challenge(b"c"), other.challenge(b"c"));
+    }
+
+    #[test]
+    fn test_message_lengths_use_the_integer_encoding() {
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        let message = [Fields::<Mersenne31, E>::Extension(E::from_canonical_u32(7))];
+
+        let mut transcript = Transcript::<Mersenne31, E>::init();
+        transcript.append_message(b"message", &message);
+        let mut other = Transcript::<Mersenne31, E>::init();
+        other.absorb(b"message", &message.len());
+        other.observe(&message);
+        assert_eq!(transcript.challenge(b"c"), other.challenge(b"c"));
+    }
+
+    #[test]
+    fn test_fork_snapshot_restore() {
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        let message = [Fields::<Mersenne31, E>::Base(
+            Mersenne31::from_canonical_u32(7),
+        )];
+
+        let mut transcript = Transcript::<Mersenne31, E>::init();
+        transcript.append_message(b"message", &message);
+
+        // a fork evolves independently of its parent
+        let mut fork = transcript.fork();
+        assert_eq!(fork.challenge(b"c"), transcript.fork().challenge(b"c"));
+        fork.append_message(b"extra", &message);
+        assert_ne!(fork.challenge(b"c"), transcript.fork().challenge(b"c"));
+
+        // restoring a snapshot replays the same challenges
+        transcript.start_recording();
+        let snapshot = transcript.snapshot();
+        let first = transcript.challenge(b"c");
+        transcript.append_message(b"extra", &message);
+        transcript.restore(&snapshot);
+        assert_eq!(transcript.challenge(b"c"), first);
+        assert_eq!(transcript.log().unwrap().len(), 2);
+
+        // a snapshot taken before recording drops everything recorded since
+        let mut transcript = Transcript::<Mersenne31, E>::init();
+        transcript.append_message(b"message", &message);
+        let expected = transcript.fork().challenge(b"c");
+        let unrecorded = transcript.snapshot();
+        transcript.start_recording();
+        transcript.append_message(b"extra", &message);
+        transcript.restore(&unrecorded);
+        assert!(transcript.log().unwrap().is_empty());
+        assert_eq!(transcript.challenge(b"c"), expected);
+    }
+
+    #[test]
+    fn test_index_and_base_sampling() {
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        let mut transcript = Transcript::<Mersenne31, E>::init_with_domain(b"queries");
+
+        for bound in [1, 5, 16, 1000] {
+            let indices = (0..32)
+                .map(|_| transcript.sample_index(bound))
+                .collect::<Vec<_>>();
+            assert!(indices.iter().all(|index| *index < bound));
+        }
+        assert!(transcript.sample_bits(4) < 16);
+        assert_ne!(transcript.sample_base(), transcript.sample_base());
+
+        // same transcript state, same samples
+        let mut other = transcript.fork();
+        assert_eq!(transcript.sample_index(77), other.sample_index(77));
+
+        // the largest bound a 31-bit field can serve
+        assert!(transcript.sample_index(1 << 30) < 1 << 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample 31 bits from a 31-bit field")]
+    fn test_index_bound_above_field_bits() {
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        Transcript::<Mersenne31, E>::init().sample_index((1 << 30) + 1);
+    }
+
+    #[test]
+    fn test_grinding() {
+        use crate::KeccakChallenger64;
+        use p3_goldilocks::Goldilocks;
+
+        type E = BinomialExtensionField<Mersenne31, 3>;
+        let mut prover = Transcript::<Mersenne31, E>::init();
+        let mut verifier = prover.fork();
+        let witness = prover.grind(8);
+        assert!(verifier.fork().check_witness(8, witness));
+        assert!(
+            !verifier
+                .fork()
+                .check_witness(8, witness + Mersenne31::one())
+        );
+
+        // prover and verifier stay in sync after the proof of work
+        assert!(verifier.check_witness(8, witness));
+        assert_eq!(prover.sample_challenge(), verifier.sample_challenge());
+
+        type GE = BinomialExtensionField<Goldilocks, 2>;
+        let mut prover = Transcript::<Goldilocks, GE, KeccakChallenger64<Goldilocks>>::new();
+        let mut verifier = prover.fork();
+        let witness = prover.grind(6);
+        assert!(verifier.check_witness(6, witness));
+
+        // the smallest witness is found, whether or not the search is parallel
+        let smallest = (0..)
+            .map(Goldilocks::from_canonical_u64)
+            .find(|candidate| verifier.fork().check_witness(10, *candidate))
+            .unwrap();
+        assert_eq!(verifier.grind(10), smallest);
+    }
+
+    #[test]
+    fn test_64_bit_transcript() {
+        use crate::KeccakChallenger64;
+        use p3_goldilocks::Goldilocks;
+
+        type E = BinomialExtensionField<Goldilocks, 2>;
+        let challenge = |val: u64| {
+            let mut transcript = Transcript::<Goldilocks, E, KeccakChallenger64<Goldilocks>>::new();
+            transcript.append_message(
+                b"value",
+                &[Fields::Base(Goldilocks::from_canonical_u64(val))],
+            );
+            transcript.challenge(b"challenge")
+        };
+
+        assert_eq!(challenge(1 << 40), challenge(1 << 40));
+        assert_ne!(challenge(1 << 40), challenge(1 << 41));
+
+        // 32-bit fields can use either backend, the serialisations differ
+        type E31 = BinomialExtensionField<Mersenne31, 3>;
+        let mut transcript_64 =
+            Transcript::<Mersenne31, E31, KeccakChallenger64<Mersenne31>>::new();
+        let mut transcript_32 = Transcript::<Mersenne31, E31>::init();
+        transcript_64.observe_base_element(&[Mersenne31::from_canonical_u32(51)]);
+        transcript_32.observe_base_element(&[Mersenne31::from_canonical_u32(51)]);
+        assert_ne!(
+            transcript_64.sample_challenge(),
+            transcript_32.sample_challenge()
+        );
+    }
 }
@@ -0,0 +1,110 @@
+//! Algebraic transcript backend, a duplex sponge over the Poseidon2 permutation on Mersenne31.
+//! Field elements are absorbed natively instead of being serialised to bytes, which keeps
+//! the transcript cheap to verify inside a circuit.
+//! The round constants are derived from a fixed ChaCha20 seed, so that every party
+//! instantiates the same permutation.
+//! Only Mersenne31 is supported: `Poseidon2Challenger` implements `TranscriptChallenger`
+//! for `Mersenne31` alone, so transcripts over other fields such as Goldilocks or BabyBear
+//! have to use the Keccak challengers.
+use std::sync::OnceLock;
+
+use p3_challenger::DuplexChallenger;
+use p3_mersenne_31::{DiffusionMatrixMersenne31, Mersenne31};
+use p3_poseidon2::{Poseidon2, Poseidon2ExternalMatrixGeneral};
+use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
+
+use crate::TranscriptChallenger;
+
+/// Width of the Poseidon2 state
+pub const POSEIDON2_WIDTH: usize = 16;
+/// Number of state elements absorbed or squeezed per permutation
+pub const POSEIDON2_RATE: usize = 8;
+/// Seed of the round constants
+const POSEIDON2_SEED: u64 = 0x5e55_1011_5eed;
+
+/// Poseidon2 permutation over Mersenne31 with the x^5 s-box
+pub type Poseidon2Mersenne31 = Poseidon2<
+    Mersenne31,
+    Poseidon2ExternalMatrixGeneral,
+    DiffusionMatrixMersenne31,
+    POSEIDON2_WIDTH,
+    5,
+>;
+
+/// Duplex challenger over `Poseidon2Mersenne31`
+pub type Poseidon2Challenger =
+    DuplexChallenger<Mersenne31, Poseidon2Mersenne31, POSEIDON2_WIDTH, POSEIDON2_RATE>;
+
+/// The transcript permutation, built once with 128-bit security round numbers
+pub fn poseidon2_permutation() -> &'static Poseidon2Mersenne31 {
+    static PERMUTATION: OnceLock<Poseidon2Mersenne31> = OnceLock::new();
+    PERMUTATION.get_or_init(|| {
+        Poseidon2::new_from_rng_128(
+            Poseidon2ExternalMatrixGeneral,
+            DiffusionMatrixMersenne31,
+            &mut ChaCha20Rng::seed_from_u64(POSEIDON2_SEED),
+        )
+    })
+}
+
+impl TranscriptChallenger<Mersenne31> for Poseidon2Challenger {
+    fn init() -> Self {
+        DuplexChallenger::new(poseidon2_permutation().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fields::Fields;
+    use p3_field::{AbstractExtensionField, AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31;
+
+    use super::Poseidon2Challenger;
+    use crate::Transcript;
+
+    type E = BinomialExtensionField<Mersenne31, 3>;
+    type PoseidonTranscript = Transcript<Mersenne31, E, Poseidon2Challenger>;
+
+    fn ext(vals: [u32; 3]) -> E {
+        E::from_base_slice(&vals.map(Mersenne31::from_canonical_u32))
+    }
+
+    #[test]
+    fn test_poseidon2_known_answers() {
+        let mut transcript = PoseidonTranscript::new();
+        transcript.observe_base_element(&[1, 2, 3].map(Mersenne31::from_canonical_u32));
+        assert_eq!(
+            transcript.sample_challenge(),
+            ext([1274124766, 272866682, 250680381])
+        );
+
+        let mut transcript = PoseidonTranscript::new_with_domain(b"poseidon2_kat");
+        transcript.append_message(
+            b"message",
+            &[Fields::Base(Mersenne31::from_canonical_u32(7))],
+        );
+        assert_eq!(
+            transcript.challenge(b"challenge"),
+            ext([1538608878, 1803093358, 286214969])
+        );
+    }
+
+    #[test]
+    fn test_poseidon2_transcript_binds_messages() {
+        let challenge = |val: u32| {
+            let mut transcript = PoseidonTranscript::new();
+            transcript.append_message(
+                b"value",
+                &[Fields::Base(Mersenne31::from_canonical_u32(val))],
+            );
+            transcript.challenge(b"challenge")
+        };
+
+        assert_eq!(challenge(5), challenge(5));
+        assert_ne!(challenge(5), challenge(6));
+
+        // consecutive challenges differ
+        let mut transcript = PoseidonTranscript::new();
+        assert_ne!(transcript.sample_challenge(), transcript.sample_challenge());
+    }
+}
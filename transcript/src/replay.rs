@@ -0,0 +1,184 @@
+//! Deterministic transcript logs for debugging prover/verifier divergence.
+//! A recording transcript logs every label, observed element and sampled challenge.
+//! Comparing the prover and verifier logs with `first_divergence` points at the first
+//! step where the two transcripts stopped agreeing.
+use std::fmt;
+
+use fields::Fields;
+use p3_field::{ExtensionField, Field};
+
+/// A single transcript operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptEvent<F: Field, E: ExtensionField<F>> {
+    /// An absorbed domain-separation label
+    Label(Vec<u8>),
+    /// An observed element, under the most recent label
+    Observe { label: Vec<u8>, value: Fields<F, E> },
+    /// A sampled challenge, under the most recent label
+    Challenge { label: Vec<u8>, value: Fields<F, E> },
+    /// A sampled integer, under the most recent label
+    Index { label: Vec<u8>, value: usize },
+}
+
+/// Events recorded by a transcript
+#[derive(Debug, Clone)]
+pub(crate) struct TranscriptLog<F: Field, E: ExtensionField<F>> {
+    pub(crate) events: Vec<TranscriptEvent<F, E>>,
+    /// Most recently absorbed label
+    label: Vec<u8>,
+}
+
+impl<F: Field, E: ExtensionField<F>> TranscriptLog<F, E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: vec![],
+            label: vec![],
+        }
+    }
+
+    pub(crate) fn label(&mut self, label: &[u8]) {
+        self.label = label.to_vec();
+        self.events.push(TranscriptEvent::Label(label.to_vec()));
+    }
+
+    pub(crate) fn observe(&mut self, value: Fields<F, E>) {
+        self.events.push(TranscriptEvent::Observe {
+            label: self.label.clone(),
+            value,
+        });
+    }
+
+    pub(crate) fn challenge(&mut self, value: Fields<F, E>) {
+        self.events.push(TranscriptEvent::Challenge {
+            label: self.label.clone(),
+            value,
+        });
+    }
+
+    pub(crate) fn index(&mut self, value: usize) {
+        self.events.push(TranscriptEvent::Index {
+            label: self.label.clone(),
+            value,
+        });
+    }
+
+    /// Drops the events after the first `len` ones
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.events.truncate(len);
+        self.label = self
+            .events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                TranscriptEvent::Label(label) => Some(label.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+    }
+}
+
+/// First step at which two transcript logs disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence<F: Field, E: ExtensionField<F>> {
+    /// Index of the mismatching step
+    pub step: usize,
+    /// Event of the first log at that step, `None` if the log ended
+    pub left: Option<TranscriptEvent<F, E>>,
+    /// Event of the second log at that step, `None` if the log ended
+    pub right: Option<TranscriptEvent<F, E>>,
+}
+
+impl<F: Field, E: ExtensionField<F>> fmt::Display for Divergence<F, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transcripts diverge at step {}: {:?} != {:?}",
+            self.step, self.left, self.right
+        )
+    }
+}
+
+/// Compares two transcript logs, returns the first mismatching step if any
+pub fn first_divergence<F: Field, E: ExtensionField<F>>(
+    left: &[TranscriptEvent<F, E>],
+    right: &[TranscriptEvent<F, E>],
+) -> Option<Divergence<F, E>> {
+    (0..left.len().max(right.len())).find_map(|step| {
+        let (left, right) = (left.get(step), right.get(step));
+        (left != right).then(|| Divergence {
+            step,
+            left: left.cloned(),
+            right: right.cloned(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fields::Fields;
+    use p3_field::{AbstractField, extension::BinomialExtensionField};
+    use p3_mersenne_31::Mersenne31 as F;
+
+    use super::{TranscriptEvent, first_divergence};
+    use crate::Transcript;
+
+    type E = BinomialExtensionField<F, 3>;
+
+    fn run(values: &[u32]) -> Vec<TranscriptEvent<F, E>> {
+        let mut transcript = Transcript::<F, E>::init();
+        transcript.start_recording();
+        for value in values {
+            transcript.append_message(b"value", &[Fields::Base(F::from_canonical_u32(*value))]);
+            transcript.challenge(b"challenge");
+        }
+        transcript.log().unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_log_records_labelled_events() {
+        let log = run(&[3]);
+        let TranscriptEvent::Challenge { label, .. } = &log[7] else {
+            panic!("expected a challenge, got {:?}", log[7]);
+        };
+        assert_eq!(label, b"challenge");
+        // the length is absorbed as four 16-bit limbs
+        let length = [1, 0, 0, 0].map(|limb| TranscriptEvent::Observe {
+            label: b"value".to_vec(),
+            value: Fields::Base(F::from_canonical_u32(limb)),
+        });
+        assert_eq!(log[0], TranscriptEvent::Label(b"value".to_vec()));
+        assert_eq!(log[1..5], length);
+        assert_eq!(
+            &log[5..6],
+            &[TranscriptEvent::Observe {
+                label: b"value".to_vec(),
+                value: Fields::Base(F::from_canonical_u32(3)),
+            },]
+        );
+        assert_eq!(log.len(), 8);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        assert_eq!(first_divergence(&run(&[1, 2]), &run(&[1, 2])), None);
+
+        // the second value differs, the log has 8 events per value
+        let divergence = first_divergence(&run(&[1, 2, 3]), &run(&[1, 4, 3])).unwrap();
+        assert_eq!(divergence.step, 13);
+        assert_eq!(
+            divergence.right,
+            Some(TranscriptEvent::Observe {
+                label: b"value".to_vec(),
+                value: Fields::Base(F::from_canonical_u32(4)),
+            })
+        );
+        assert!(
+            divergence
+                .to_string()
+                .starts_with("transcripts diverge at step 13")
+        );
+
+        let divergence = first_divergence(&run(&[1]), &run(&[1, 2])).unwrap();
+        assert_eq!((divergence.step, divergence.left), (8, None));
+    }
+}